@@ -0,0 +1,131 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use text_placeholder::Template;
+
+/// Generates a JSON-like template of roughly `size` bytes, with a
+/// placeholder every few lines and plenty of single braces in between.
+fn generate_template(size: usize) -> String {
+    let mut text = String::with_capacity(size + 128);
+    let mut index = 0;
+
+    while text.len() < size {
+        text.push_str("{\"id\": ");
+        text.push_str(&index.to_string());
+        text.push_str(", \"description\": \"some generated text that is long enough\", ");
+        text.push_str("\"name\": \"{{ name_");
+        text.push_str(&(index % 100).to_string());
+        text.push_str(" }}\"},\n");
+        index += 1;
+    }
+
+    text
+}
+
+/// The tokenizer as it was released before searching for the first byte of
+/// the boundaries, copied verbatim as a reference point for the benchmarks.
+mod baseline {
+    enum State {
+        Text,
+        Placeholder,
+    }
+
+    #[derive(Clone, PartialEq, Debug)]
+    pub enum Token<'t> {
+        Text(&'t str),
+        Placeholder(&'t str),
+    }
+
+    pub struct TokenIterator<'t> {
+        text: &'t str,
+        state: State,
+        start: &'t str,
+        end: &'t str,
+    }
+
+    impl<'t> TokenIterator<'t> {
+        pub fn new(text: &'t str, start: &'t str, end: &'t str) -> Self {
+            Self {
+                text,
+                start,
+                end,
+                state: State::Text,
+            }
+        }
+
+        fn parse_text(&mut self) -> Token<'t> {
+            let token: Token;
+
+            if let Some(placeholder_index) = self.text.find(self.start) {
+                token = Token::Text(&self.text[..placeholder_index]);
+                self.text = &self.text[placeholder_index..];
+                self.state = State::Placeholder;
+            } else {
+                token = Token::Text(self.text);
+                self.text = "";
+            }
+
+            token
+        }
+
+        fn parse_placeholder(&mut self) -> Token<'t> {
+            let token: Token;
+            self.state = State::Text;
+
+            if let Some(placeholder_index) = self.text.find(self.end) {
+                token = Token::Placeholder(
+                    self.text[self.start.len()..placeholder_index]
+                        .trim_start_matches(' ')
+                        .trim_end_matches(' '),
+                );
+                let new_position = placeholder_index + self.end.len();
+                self.text = &self.text[new_position..];
+            } else {
+                token = Token::Text(self.text);
+                self.text = "";
+            }
+
+            token
+        }
+    }
+
+    impl<'t> Iterator for TokenIterator<'t> {
+        type Item = Token<'t>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.text.is_empty() {
+                return None;
+            }
+
+            match self.state {
+                State::Text => Some(self.parse_text()),
+                State::Placeholder => Some(self.parse_placeholder()),
+            }
+        }
+    }
+}
+
+fn parse(c: &mut Criterion) {
+    let text = generate_template(4 * 1024 * 1024);
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.sample_size(20);
+
+    group.bench_function("baseline_tokenizer", |b| {
+        b.iter(|| {
+            baseline::TokenIterator::new(black_box(&text), "{{", "}}")
+                .collect::<Vec<baseline::Token>>()
+        })
+    });
+
+    group.bench_function("template_new", |b| {
+        b.iter(|| Template::new(black_box(&text)))
+    });
+
+    group.bench_function("template_new_with_placeholders", |b| {
+        b.iter(|| Template::new_with_placeholders(black_box(&text), &[("${", "}"), ("{{", "}}")]))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,242 @@
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+use core::future::{poll_fn, Future};
+use core::pin::Pin;
+use core::task::Poll;
+
+use crate::error::{Error, Result};
+use crate::Template;
+
+impl<'t> Template<'t> {
+    /// Asynchronous version of [`Template::fill_with_function`], for values
+    /// coming from a database, a cache or any other asynchronous source.
+    ///
+    /// The function is called once per unique placeholder name and up to
+    /// `concurrency` of the returned futures are polled at the same time (a
+    /// `concurrency` of 0 behaves like 1). The output is assembled in token
+    /// order once every value is resolved. The futures are polled within the
+    /// returned future, so any executor can drive it.
+    ///
+    /// Placeholders without an associated value (the future resolves to
+    /// `None`) will result in a `Error::MissingValue`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// # use std::borrow::Cow;
+    /// # use std::future::Future;
+    /// # use std::sync::Arc;
+    /// # use std::task::{Context, Poll, Wake};
+    /// # struct NoopWaker;
+    /// # impl Wake for NoopWaker {
+    /// #     fn wake(self: Arc<Self>) {}
+    /// # }
+    /// # fn block_on<F: Future>(future: F) -> F::Output {
+    /// #     let waker = Arc::new(NoopWaker).into();
+    /// #     let mut cx = Context::from_waker(&waker);
+    /// #     let mut future = Box::pin(future);
+    /// #     loop {
+    /// #         if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    /// async fn lookup(key: &str) -> Option<Cow<'static, str>> {
+    ///     match key {
+    ///         "name" => Some(Cow::Borrowed("Ana")),
+    ///         "city" => Some(Cow::Borrowed("Lisbon")),
+    ///         _ => None,
+    ///     }
+    /// }
+    ///
+    /// let template = Template::new("{{name}} lives in {{city}}, hi {{name}}!");
+    /// let result = block_on(template.fill_with_async_function(lookup, 4));
+    ///
+    /// assert_eq!(result.unwrap(), "Ana lives in Lisbon, hi Ana!");
+    /// ```
+    pub async fn fill_with_async_function<'a, F, Fut>(
+        &self,
+        mut replacements: F,
+        concurrency: usize,
+    ) -> Result<String>
+    where
+        F: FnMut(&'t str) -> Fut,
+        Fut: Future<Output = Option<Cow<'a, str>>>,
+    {
+        let compiled = self.compile();
+        let names = compiled.names();
+        let concurrency = concurrency.max(1);
+
+        let mut values: Vec<Option<Cow<'a, str>>> = names.iter().map(|_| None).collect();
+        let mut pending: Vec<(usize, Pin<Box<Fut>>)> = Vec::with_capacity(concurrency);
+        let mut next = 0;
+
+        poll_fn(|cx| loop {
+            while pending.len() < concurrency && next < names.len() {
+                pending.push((next, Box::pin(replacements(names[next]))));
+                next += 1;
+            }
+
+            let polled = pending.len();
+            pending.retain_mut(|(slot, future)| match future.as_mut().poll(cx) {
+                Poll::Ready(value) => {
+                    values[*slot] = value;
+                    false
+                }
+                Poll::Pending => true,
+            });
+
+            if pending.is_empty() && next == names.len() {
+                return Poll::Ready(());
+            }
+
+            // Nothing completed, every pending future registered the waker.
+            if pending.len() == polled {
+                return Poll::Pending;
+            }
+        })
+        .await;
+
+        let slots = names
+            .iter()
+            .zip(values)
+            .map(|(name, value)| value.ok_or_else(|| Error::missing_value(self.source, name)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(compiled.render(&slots))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        borrow::Cow, borrow::ToOwned, boxed::Box, rc::Rc, string::ToString, sync::Arc, vec::Vec,
+    };
+    use core::cell::{Cell, RefCell};
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::task::{Context, Poll, Waker};
+
+    use crate::Template;
+
+    struct NoopWaker;
+
+    impl alloc::task::Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// Returns `Pending` the given number of times before completing.
+    struct YieldTimes(usize);
+
+    impl Future for YieldTimes {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 == 0 {
+                return Poll::Ready(());
+            }
+
+            self.0 -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn test_fill_with_async_function() {
+        let template = Template::new("{{a}}-{{b}}-{{a}}-{{c}}");
+        let calls = RefCell::new(Vec::new());
+
+        let result = block_on(template.fill_with_async_function(
+            |key| {
+                calls.borrow_mut().push(key);
+                async move {
+                    // Later placeholders resolve first.
+                    YieldTimes(3 - key.len().min(3)).await;
+                    Some(Cow::Owned(key.to_uppercase()))
+                }
+            },
+            2,
+        ));
+
+        assert_eq!(result.unwrap(), "A-B-A-C");
+        assert_eq!(*calls.borrow(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_fill_with_async_function_out_of_order() {
+        let template = Template::new("{{slow}} {{fast}}");
+
+        let result = block_on(template.fill_with_async_function(
+            |key| async move {
+                YieldTimes(if key == "slow" { 5 } else { 0 }).await;
+                Some(Cow::Borrowed(key))
+            },
+            2,
+        ));
+
+        assert_eq!(result.unwrap(), "slow fast");
+    }
+
+    #[test]
+    fn test_fill_with_async_function_bounded_concurrency() {
+        let template = Template::new("{{a}} {{b}} {{c}} {{d}} {{e}}");
+        let in_flight = Rc::new(Cell::new(0));
+        let max_in_flight = Rc::new(Cell::new(0));
+
+        for (concurrency, expected) in [(0, 1), (1, 1), (2, 2), (10, 5)] {
+            max_in_flight.set(0);
+
+            let result = block_on(template.fill_with_async_function(
+                |key| {
+                    let in_flight = in_flight.clone();
+                    let max_in_flight = max_in_flight.clone();
+
+                    async move {
+                        in_flight.set(in_flight.get() + 1);
+                        max_in_flight.set(max_in_flight.get().max(in_flight.get()));
+                        YieldTimes(2).await;
+                        in_flight.set(in_flight.get() - 1);
+                        Some(Cow::Borrowed(key))
+                    }
+                },
+                concurrency,
+            ));
+
+            assert_eq!(result.unwrap(), "a b c d e");
+            assert_eq!(max_in_flight.get(), expected);
+        }
+    }
+
+    #[test]
+    fn test_fill_with_async_function_missing_value() {
+        let template = Template::new("hello {{first}} {{second}}");
+
+        let result = block_on(template.fill_with_async_function(
+            |key| async move {
+                if key == "first" {
+                    Some(Cow::Borrowed("world"))
+                } else {
+                    None
+                }
+            },
+            2,
+        ));
+
+        assert_eq!(
+            result.map_err(|e| e.to_string()),
+            Err("Error while replacing placeholder. Reason: missing value for placeholder named 'second'.".to_owned())
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use alloc::string::String;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::context::PlaceholderContext;
+use crate::error::Result;
+use crate::token_iterator::Token;
+use crate::Template;
+
+impl<'t> Template<'t> {
+    /// Fill the template once per context yielded by `contexts`, returning an
+    /// iterator over the results in the same order.
+    ///
+    /// Each context behaves like in [`Template::fill_with_context_strict`], a
+    /// placeholder without an associated value results in an error for that
+    /// context only and the remaining contexts are still rendered. The parsed
+    /// template is shared by every render and each output buffer is sized
+    /// after the longest output so far.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// # #[cfg(feature = "std")]
+    /// # use std::collections::HashMap;
+    /// # #[cfg(not(feature = "std"))]
+    /// # use hashbrown::HashMap;
+    /// let template = Template::new("Hello {{name}}!");
+    ///
+    /// let contexts = vec![
+    ///     HashMap::from([("name", "Ana")]),
+    ///     HashMap::new(),
+    ///     HashMap::from([("name", "Bo")]),
+    /// ];
+    ///
+    /// let results: Vec<_> = template.render_many(&contexts).collect();
+    ///
+    /// assert_eq!(results[0].as_deref().unwrap(), "Hello Ana!");
+    /// assert!(results[1].is_err());
+    /// assert_eq!(results[2].as_deref().unwrap(), "Hello Bo!");
+    /// ```
+    pub fn render_many<'s, I>(&'s self, contexts: I) -> impl Iterator<Item = Result<String>> + 's
+    where
+        I: IntoIterator,
+        I::IntoIter: 's,
+        I::Item: PlaceholderContext,
+    {
+        let mut capacity = self.text_len();
+
+        contexts.into_iter().map(move |context| {
+            let result = self.render_with_capacity(&context, capacity)?;
+            capacity = capacity.max(result.len());
+
+            Ok(result)
+        })
+    }
+
+    /// Parallel version of [`Template::render_many`] backed by `rayon`,
+    /// available with the `rayon` feature.
+    ///
+    /// Results can be collected in the same order as the contexts:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// # use std::collections::HashMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let template = Template::new("Hello {{name}}!");
+    /// let contexts: Vec<_> = (0..100)
+    ///     .map(|i| HashMap::from([("name".to_string(), i.to_string())]))
+    ///     .collect();
+    ///
+    /// let results: Vec<_> = template.par_render_many(&contexts).collect();
+    ///
+    /// assert_eq!(results[42].as_deref().unwrap(), "Hello 42!");
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_render_many<'s, I>(
+        &'s self,
+        contexts: I,
+    ) -> impl ParallelIterator<Item = Result<String>> + 's
+    where
+        I: IntoParallelIterator,
+        I::Iter: 's,
+        I::Item: PlaceholderContext,
+    {
+        let capacity = self.text_len();
+
+        contexts
+            .into_par_iter()
+            .map(move |context| self.render_with_capacity(&context, capacity))
+    }
+
+    fn render_with_capacity<C>(&self, context: &C, capacity: usize) -> Result<String>
+    where
+        C: PlaceholderContext,
+    {
+        let mut result = String::with_capacity(capacity);
+        self.fill_into(&mut result, |s| context.get(s))
+            .map_err(|err| err.with_suggestions(context.keys()))?;
+
+        Ok(result)
+    }
+
+    /// Length of the template's text, excluding placeholders.
+    fn text_len(&self) -> usize {
+        self.tokens
+            .iter()
+            .map(|token| match token {
+                Token::Text(s) => s.len(),
+                Token::Placeholder(..) => 0,
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned, string::ToString, vec::Vec};
+
+    #[cfg(feature = "std")]
+    use std::collections::{BTreeMap, HashMap};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::collections::BTreeMap;
+    #[cfg(not(feature = "std"))]
+    use hashbrown::HashMap;
+
+    use crate::Template;
+
+    #[test]
+    fn test_render_many() {
+        let template = Template::new("{{greeting}} {{name}}!");
+        let contexts: Vec<HashMap<&str, &str>> = (0..3)
+            .map(|i| {
+                let mut table = HashMap::new();
+                table.insert("greeting", "hello");
+                table.insert("name", ["one", "two", "three"][i]);
+                table
+            })
+            .collect();
+
+        let results: Vec<_> = template
+            .render_many(&contexts)
+            .map(|result| result.unwrap())
+            .collect();
+
+        assert_eq!(results, vec!["hello one!", "hello two!", "hello three!"]);
+    }
+
+    #[test]
+    fn test_render_many_errors_do_not_abort() {
+        let template = Template::new("hello {{name}}");
+        let mut valid = BTreeMap::new();
+        valid.insert("name".to_string(), "world".to_string());
+        let contexts = vec![valid.clone(), BTreeMap::new(), valid];
+
+        let results: Vec<_> = template
+            .render_many(contexts)
+            .map(|result| result.map_err(|e| e.to_string()))
+            .collect();
+
+        assert_eq!(
+            results,
+            vec![
+                Ok("hello world".to_owned()),
+                Err("Error while replacing placeholder. Reason: missing value for placeholder named 'name'.".to_owned()),
+                Ok("hello world".to_owned()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_render_many() {
+        use rayon::iter::ParallelIterator;
+
+        let template = Template::new("item {{index}}");
+        let contexts: Vec<HashMap<String, String>> = (0..1000)
+            .map(|i| {
+                let mut table = HashMap::new();
+                if i % 10 != 0 {
+                    table.insert("index".to_string(), i.to_string());
+                }
+                table
+            })
+            .collect();
+
+        let results: Vec<_> = template.par_render_many(&contexts).collect();
+
+        assert_eq!(results.len(), 1000);
+        for (i, result) in results.iter().enumerate() {
+            if i % 10 == 0 {
+                assert!(result.is_err());
+            } else {
+                assert_eq!(result.as_ref().unwrap(), &format!("item {i}"));
+            }
+        }
+    }
+}
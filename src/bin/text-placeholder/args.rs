@@ -0,0 +1,305 @@
+use std::ffi::OsString;
+use std::fmt;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Renders a template with values from the command line, files or the environment.
+
+Usage: text-placeholder [OPTIONS] [TEMPLATE]
+       text-placeholder check [OPTIONS] <TEMPLATE>...
+       text-placeholder vars [--json] [--start <START>] [--end <END>] <TEMPLATE>...
+
+Commands:
+  check                  Check that templates parse strictly and, when values are
+                         given, that every placeholder has a value
+  vars                   List the placeholders of each template
+
+Arguments:
+  [TEMPLATE]             Template file, reads stdin when missing or `-`
+
+Options:
+      --var <KEY=VALUE>  Value for a placeholder, can be repeated
+      --vars <FILE>      JSON (.json), TOML (.toml) or env (.env) file with values,
+                         can be repeated
+      --env              Use the environment variables as values
+      --start <START>    Starting boundary of placeholders [default: {{]
+      --end <END>        Ending boundary of placeholders [default: }}]
+      --strict           Fail on malformed templates and missing values
+  -o, --output <FILE>    Write to a file instead of stdout
+      --json             Print the placeholders as JSON (vars only)
+  -h, --help             Print help
+
+Values from --var take precedence over files, which take precedence over
+the environment. Later files take precedence over earlier ones.";
+
+/// Where a template is read from or rendered to, `None` meaning stdin or
+/// stdout.
+pub type Location = Option<PathBuf>;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Command {
+    #[default]
+    Render,
+    Check,
+    Vars,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    /// Template files, stdin when empty.
+    pub templates: Vec<PathBuf>,
+    pub vars: Vec<(String, String)>,
+    pub var_files: Vec<PathBuf>,
+    pub env: bool,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub strict: bool,
+    pub output: Location,
+    pub json: bool,
+    pub help: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ArgsError(String);
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Args {
+    pub fn parse<I>(args: I) -> Result<Self, ArgsError>
+    where
+        I: IntoIterator<Item = OsString>,
+    {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter().peekable();
+        let mut positional_only = false;
+
+        let command = match args.peek().and_then(|arg| arg.to_str()) {
+            Some("check") => Some(Command::Check),
+            Some("vars") => Some(Command::Vars),
+            _ => None,
+        };
+
+        if let Some(command) = command {
+            parsed.command = command;
+            args.next();
+        }
+
+        while let Some(arg) = args.next() {
+            let arg = arg
+                .into_string()
+                .map_err(|arg| ArgsError(format!("invalid argument {:?}", arg)))?;
+
+            if positional_only || arg == "-" || !arg.starts_with('-') {
+                if parsed.command == Command::Render && !parsed.templates.is_empty() {
+                    return Err(ArgsError(format!("unexpected argument '{}'", arg)));
+                }
+
+                parsed.templates.push(PathBuf::from(arg));
+                continue;
+            }
+
+            // Accepts both `--option value` and `--option=value`, as well as
+            // `-o=value` for the short option.
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") || name == "-o" => {
+                    (name, Some(value.to_string()))
+                }
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || match inline.clone() {
+                Some(value) => Ok(value),
+                None => args
+                    .next()
+                    .and_then(|value| value.into_string().ok())
+                    .ok_or_else(|| ArgsError(format!("a value is required for '{}'", name))),
+            };
+            let flag = || match inline {
+                Some(_) => Err(ArgsError(format!("'{}' does not take a value", name))),
+                None => Ok(true),
+            };
+
+            match name {
+                "--" => positional_only = true,
+                "--var" => {
+                    let var = value()?;
+                    let (key, value) = var.split_once('=').ok_or_else(|| {
+                        ArgsError(format!("invalid --var '{}', expected KEY=VALUE", var))
+                    })?;
+                    parsed.vars.push((key.to_string(), value.to_string()));
+                }
+                "--vars" => parsed.var_files.push(PathBuf::from(value()?)),
+                "--env" => parsed.env = flag()?,
+                "--start" => parsed.start = Some(value()?),
+                "--end" => parsed.end = Some(value()?),
+                "--strict" => parsed.strict = flag()?,
+                "-o" | "--output" => parsed.output = Some(PathBuf::from(value()?)),
+                "--json" => parsed.json = flag()?,
+                "-h" | "--help" => parsed.help = flag()?,
+                _ => return Err(ArgsError(format!("unexpected argument '{}'", arg))),
+            }
+        }
+
+        if parsed.command == Command::Render && parsed.templates == [PathBuf::from("-")] {
+            parsed.templates.clear();
+        }
+
+        if parsed.json && parsed.command != Command::Vars {
+            return Err(ArgsError(
+                "'--json' is only supported by the vars command".to_string(),
+            ));
+        }
+
+        if parsed.command != Command::Render && parsed.templates.is_empty() {
+            return Err(ArgsError("at least one template is required".to_string()));
+        }
+
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Args, ArgsError, Command};
+    use std::path::PathBuf;
+
+    fn parse(args: &[&str]) -> Result<Args, ArgsError> {
+        Args::parse(args.iter().map(Into::into))
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = parse(&[
+            "--var",
+            "name=Ana",
+            "--var=greeting=hello=hi",
+            "--vars",
+            "values.toml",
+            "--env",
+            "--start",
+            "[",
+            "--end=]",
+            "--strict",
+            "-o",
+            "out.txt",
+            "template.txt",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            args,
+            Args {
+                command: Command::Render,
+                templates: vec![PathBuf::from("template.txt")],
+                vars: vec![
+                    ("name".to_string(), "Ana".to_string()),
+                    ("greeting".to_string(), "hello=hi".to_string()),
+                ],
+                var_files: vec![PathBuf::from("values.toml")],
+                env: true,
+                start: Some("[".to_string()),
+                end: Some("]".to_string()),
+                strict: true,
+                output: Some(PathBuf::from("out.txt")),
+                json: false,
+                help: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_stdin() {
+        assert_eq!(parse(&[]).unwrap(), Args::default());
+        assert_eq!(parse(&["-"]).unwrap(), Args::default());
+        assert_eq!(
+            parse(&["--", "--template"]).unwrap().templates,
+            [PathBuf::from("--template")]
+        );
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let args = parse(&["vars", "--json", "a.txt", "b.txt"]).unwrap();
+
+        assert_eq!(args.command, Command::Vars);
+        assert_eq!(
+            args.templates,
+            [PathBuf::from("a.txt"), PathBuf::from("b.txt")]
+        );
+        assert!(args.json);
+
+        let args = parse(&["check", "--vars", "values.env", "a.txt"]).unwrap();
+
+        assert_eq!(args.command, Command::Check);
+        assert_eq!(args.var_files, [PathBuf::from("values.env")]);
+
+        // Only the first argument selects a command.
+        assert_eq!(
+            parse(&["a.txt", "check"]).map(|args| args.command),
+            Err(ArgsError("unexpected argument 'check'".to_string()))
+        );
+        assert_eq!(
+            parse(&["check"]),
+            Err(ArgsError("at least one template is required".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse(&["--var", "name"]),
+            Err(ArgsError(
+                "invalid --var 'name', expected KEY=VALUE".to_string()
+            ))
+        );
+        assert_eq!(
+            parse(&["--start"]),
+            Err(ArgsError("a value is required for '--start'".to_string()))
+        );
+        assert_eq!(
+            parse(&["--unknown"]),
+            Err(ArgsError("unexpected argument '--unknown'".to_string()))
+        );
+        assert_eq!(
+            parse(&["a.txt", "b.txt"]),
+            Err(ArgsError("unexpected argument 'b.txt'".to_string()))
+        );
+        assert_eq!(
+            parse(&["--env=1"]),
+            Err(ArgsError("'--env' does not take a value".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_output_forms() {
+        for args in [
+            &["-o", "out.txt"][..],
+            &["-o=out.txt"],
+            &["--output", "out.txt"],
+            &["--output=out.txt"],
+        ] {
+            assert_eq!(
+                parse(args).unwrap().output,
+                Some(PathBuf::from("out.txt")),
+                "{:?}",
+                args
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_json_only_for_vars() {
+        for args in [&["--json"][..], &["check", "--json", "a.txt"]] {
+            assert_eq!(
+                parse(args),
+                Err(ArgsError(
+                    "'--json' is only supported by the vars command".to_string()
+                ))
+            );
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::error::Error;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use serde_json::json;
+use text_placeholder::{Error as TemplateError, Template};
+
+use crate::args::Args;
+use crate::vars::Vars;
+use crate::{boundaries, collect_vars, read};
+
+/// Lists the placeholders of each template.
+pub fn vars(args: &Args) -> Result<ExitCode, Box<dyn Error>> {
+    let (start, end) = boundaries(args);
+    let mut files = Vec::new();
+
+    for path in &args.templates {
+        let text = read(Some(path))?;
+        let placeholders: Vec<String> = Template::new_with_placeholder(&text, start, end)
+            .placeholders()
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        files.push((path.display().to_string(), placeholders));
+    }
+
+    let mut stdout = io::stdout().lock();
+
+    if args.json {
+        let files: Vec<_> = files
+            .iter()
+            .map(|(file, placeholders)| json!({ "file": file, "placeholders": placeholders }))
+            .collect();
+
+        serde_json::to_writer_pretty(&mut stdout, &files)?;
+        writeln!(stdout)?;
+    } else {
+        for (file, placeholders) in &files {
+            writeln!(stdout, "{}:", file)?;
+
+            for placeholder in placeholders {
+                writeln!(stdout, "  {}", placeholder)?;
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Checks that each template parses strictly and, when values are given,
+/// that every placeholder has a value. Prints a diagnostic per problem and
+/// fails when there is any.
+pub fn check(args: &Args) -> Result<ExitCode, Box<dyn Error>> {
+    let (start, end) = boundaries(args);
+    let has_vars = args.env || !args.var_files.is_empty() || !args.vars.is_empty();
+    let vars = if has_vars {
+        Some(collect_vars(args)?)
+    } else {
+        None
+    };
+
+    let mut failed = false;
+    let mut stderr = io::stderr().lock();
+
+    for path in &args.templates {
+        let text = read(Some(path))?;
+
+        for (offset, message) in diagnostics(&text, start, end, vars.as_ref()) {
+            let (line, column) = position(&text, offset);
+            writeln!(
+                stderr,
+                "{}:{}:{}: {}",
+                path.display(),
+                line,
+                column,
+                message
+            )?;
+            failed = true;
+        }
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// Returns the problems found in `text` along with their byte offset.
+fn diagnostics(text: &str, start: &str, end: &str, vars: Option<&Vars>) -> Vec<(usize, String)> {
+    let template = match Template::new_with_placeholder_strict(text, start, end) {
+        Ok(template) => template,
+        Err(err) => {
+            let offset = match &err {
+                TemplateError::InvalidName { span, .. }
+                | TemplateError::Unclosed { span }
+                | TemplateError::InvalidDelimiters { span } => span.start,
+                _ => 0,
+            };
+
+            return vec![(offset, err.to_string())];
+        }
+    };
+
+    let vars = match vars {
+        Some(vars) => vars,
+        None => return Vec::new(),
+    };
+
+    template
+        .validate(vars.keys().map(String::as_str))
+        .missing
+        .into_iter()
+        .map(|name| {
+            // Placeholder names are slices of the template text.
+            let offset = name.as_ptr() as usize - text.as_ptr() as usize;
+            (
+                offset,
+                format!("missing value for placeholder named '{}'", name),
+            )
+        })
+        .collect()
+}
+
+/// Line and column, both starting at 1, of the byte `offset` within `text`.
+/// Columns are counted in chars.
+fn position(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diagnostics, position};
+    use crate::vars::Vars;
+
+    #[test]
+    fn test_position() {
+        let text = "first\nsé{{x}}\n\n{{y}}";
+
+        assert_eq!(position(text, 0), (1, 1));
+        assert_eq!(position(text, 5), (1, 6));
+        assert_eq!(position(text, 6), (2, 1));
+        assert_eq!(position(text, text.find("{{x").unwrap()), (2, 3));
+        assert_eq!(position(text, text.find("{{y").unwrap()), (4, 1));
+    }
+
+    #[test]
+    fn test_diagnostics_parsing() {
+        assert!(diagnostics("Hello {{name}}", "{{", "}}", None).is_empty());
+        assert_eq!(
+            diagnostics("Hello\n{{name", "{{", "}}", None),
+            [(
+                6,
+                "Error while parsing template. Reason: unclosed placeholder at 6..12.".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_missing_values() {
+        let mut vars = Vars::new();
+        vars.insert("name".to_string(), "Ana".to_string());
+
+        let text = "Hello [name]\n[city], [name] [zip]";
+
+        assert_eq!(
+            diagnostics(text, "[", "]", Some(&vars)),
+            [
+                (14, "missing value for placeholder named 'city'".to_string()),
+                (29, "missing value for placeholder named 'zip'".to_string()),
+            ]
+        );
+        assert!(diagnostics("[name]", "[", "]", Some(&vars)).is_empty());
+    }
+}
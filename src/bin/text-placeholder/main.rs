@@ -0,0 +1,120 @@
+//! `text-placeholder` renders a template with values given on the command
+//! line, read from files or taken from the environment. Available with the
+//! `cli` feature, run `text-placeholder --help` for the usage.
+
+mod args;
+mod commands;
+mod vars;
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use text_placeholder::Template;
+
+use args::{Args, Command, Location, USAGE};
+use vars::Vars;
+
+const DEFAULT_START: &str = "{{";
+const DEFAULT_END: &str = "}}";
+
+fn main() -> ExitCode {
+    let args = match Args::parse(env::args_os().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {}\n\nFor more information, try '--help'.", err);
+            return ExitCode::from(2);
+        }
+    };
+
+    if args.help {
+        // Ignores a closed stdout, such as when piped to `head`.
+        let _ = writeln!(io::stdout(), "{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let result = match args.command {
+        Command::Render => render(&args),
+        Command::Check => commands::check(&args),
+        Command::Vars => commands::vars(&args),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn render(args: &Args) -> Result<ExitCode, Box<dyn Error>> {
+    let text = read(args.templates.first().map(|path| path.as_path()))?;
+    let vars = collect_vars(args)?;
+    let (start, end) = boundaries(args);
+
+    let output = if args.strict {
+        Template::new_with_placeholder_strict(&text, start, end)?.fill_with_context_strict(&vars)?
+    } else {
+        Template::new_with_placeholder(&text, start, end).fill_with_context(&vars)
+    };
+
+    write(&args.output, &output)?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// The boundaries of placeholders given with `--start` and `--end`.
+fn boundaries(args: &Args) -> (&str, &str) {
+    (
+        args.start.as_deref().unwrap_or(DEFAULT_START),
+        args.end.as_deref().unwrap_or(DEFAULT_END),
+    )
+}
+
+/// Merges the values from the environment, the files and `--var`, in
+/// increasing order of precedence.
+fn collect_vars(args: &Args) -> Result<Vars, Box<dyn Error>> {
+    let mut vars = Vars::new();
+
+    if args.env {
+        vars.extend(env::vars());
+    }
+
+    for file in &args.var_files {
+        vars::load(file, &mut vars)?;
+    }
+
+    vars.extend(args.vars.iter().cloned());
+
+    Ok(vars)
+}
+
+/// Reads a template from `path`, or from stdin when `None`.
+fn read(path: Option<&Path>) -> Result<String, Box<dyn Error>> {
+    match path {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|err| format!("could not read '{}': {}", path.display(), err).into()),
+        None => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text)?;
+            Ok(text)
+        }
+    }
+}
+
+fn write(location: &Location, output: &str) -> Result<(), Box<dyn Error>> {
+    match location {
+        Some(path) => fs::write(path, output)
+            .map_err(|err| format!("could not write '{}': {}", path.display(), err).into()),
+        None => {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(output.as_bytes())?;
+            stdout.flush()?;
+            Ok(())
+        }
+    }
+}
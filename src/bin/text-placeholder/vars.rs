@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Values for placeholders, keyed by placeholder name.
+pub type Vars = HashMap<String, String>;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Loads the values of a JSON, TOML or env file, chosen by its extension.
+pub fn load(path: &Path, vars: &mut Vars) -> Result<()> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("could not read '{}': {}", path.display(), err))?;
+    let extension = path.extension().and_then(|extension| extension.to_str());
+
+    let result = match extension {
+        Some("json") => serde_json::from_str(&text)
+            .map_err(Into::into)
+            .and_then(|value| flatten_json("", &value, vars)),
+        Some("toml") => toml::from_str(&text)
+            .map_err(Into::into)
+            .and_then(|value| flatten_toml("", &value, vars)),
+        Some("env") => parse_env(&text, vars),
+        _ => Err("unknown file type, expected a .json, .toml or .env file".into()),
+    };
+
+    result.map_err(|err| format!("could not load '{}': {}", path.display(), err).into())
+}
+
+/// Inserts the scalar values of `value`, nested objects produce dotted keys
+/// such as `user.name`.
+fn flatten_json(prefix: &str, value: &serde_json::Value, vars: &mut Vars) -> Result<()> {
+    use serde_json::Value;
+
+    let scalar = match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                flatten_json(&join(prefix, key), value, vars)?;
+            }
+            return Ok(());
+        }
+        Value::String(value) => value.clone(),
+        Value::Number(value) => value.to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Null => return Ok(()),
+        Value::Array(_) => return Err(unsupported(prefix, "an array")),
+    };
+
+    insert(prefix, scalar, vars)
+}
+
+/// TOML version of [`flatten_json`].
+fn flatten_toml(prefix: &str, value: &toml::Value, vars: &mut Vars) -> Result<()> {
+    use toml::Value;
+
+    let scalar = match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                flatten_toml(&join(prefix, key), value, vars)?;
+            }
+            return Ok(());
+        }
+        Value::String(value) => value.clone(),
+        Value::Integer(value) => value.to_string(),
+        Value::Float(value) => value.to_string(),
+        Value::Boolean(value) => value.to_string(),
+        Value::Datetime(value) => value.to_string(),
+        Value::Array(_) => return Err(unsupported(prefix, "an array")),
+    };
+
+    insert(prefix, scalar, vars)
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+fn insert(key: &str, value: String, vars: &mut Vars) -> Result<()> {
+    if key.is_empty() {
+        return Err("expected a table of values".into());
+    }
+
+    vars.insert(key.to_string(), value);
+    Ok(())
+}
+
+fn unsupported(key: &str, kind: &str) -> Box<dyn Error> {
+    format!(
+        "the value of '{}' is {}, which cannot replace a placeholder",
+        key, kind
+    )
+    .into()
+}
+
+/// Parses `KEY=VALUE` lines, ignoring blank lines and `#` comments. Lines
+/// may start with `export` and values may be wrapped in quotes.
+fn parse_env(text: &str, vars: &mut Vars) -> Result<()> {
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("line {}: expected KEY=VALUE", number + 1))?;
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|quote| {
+                value
+                    .strip_prefix(*quote)
+                    .and_then(|value| value.strip_suffix(*quote))
+            })
+            .unwrap_or(value);
+
+        vars.insert(key.trim().to_string(), value.to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{flatten_json, flatten_toml, parse_env, Vars};
+
+    #[test]
+    fn test_flatten_json() {
+        let value = serde_json::json!({
+            "name": "Ana",
+            "age": 42,
+            "admin": true,
+            "missing": null,
+            "address": { "city": "Lisbon", "zip": { "code": "1000" } }
+        });
+        let mut vars = Vars::new();
+
+        flatten_json("", &value, &mut vars).unwrap();
+
+        assert_eq!(vars.len(), 5);
+        assert_eq!(vars["name"], "Ana");
+        assert_eq!(vars["age"], "42");
+        assert_eq!(vars["admin"], "true");
+        assert_eq!(vars["address.city"], "Lisbon");
+        assert_eq!(vars["address.zip.code"], "1000");
+    }
+
+    #[test]
+    fn test_flatten_json_errors() {
+        let mut vars = Vars::new();
+
+        assert_eq!(
+            flatten_json("", &serde_json::json!({ "list": [1] }), &mut vars)
+                .unwrap_err()
+                .to_string(),
+            "the value of 'list' is an array, which cannot replace a placeholder"
+        );
+        assert_eq!(
+            flatten_json("", &serde_json::json!("text"), &mut vars)
+                .unwrap_err()
+                .to_string(),
+            "expected a table of values"
+        );
+    }
+
+    #[test]
+    fn test_flatten_toml() {
+        let value: toml::Value = toml::from_str(
+            r#"
+            name = "Ana"
+            ratio = 0.5
+
+            [address]
+            city = "Lisbon"
+            "#,
+        )
+        .unwrap();
+        let mut vars = Vars::new();
+
+        flatten_toml("", &value, &mut vars).unwrap();
+
+        assert_eq!(vars.len(), 3);
+        assert_eq!(vars["name"], "Ana");
+        assert_eq!(vars["ratio"], "0.5");
+        assert_eq!(vars["address.city"], "Lisbon");
+    }
+
+    #[test]
+    fn test_parse_env() {
+        let mut vars = Vars::new();
+
+        parse_env(
+            "# comment\n\nNAME=Ana\nexport CITY = \"Lisbon\"\nQUOTE='a=b'\nEMPTY=\n",
+            &mut vars,
+        )
+        .unwrap();
+
+        assert_eq!(vars.len(), 4);
+        assert_eq!(vars["NAME"], "Ana");
+        assert_eq!(vars["CITY"], "Lisbon");
+        assert_eq!(vars["QUOTE"], "a=b");
+        assert_eq!(vars["EMPTY"], "");
+
+        assert_eq!(
+            parse_env("NAME", &mut vars).unwrap_err().to_string(),
+            "line 1: expected KEY=VALUE"
+        );
+    }
+}
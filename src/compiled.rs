@@ -0,0 +1,233 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(not(feature = "std"))]
+use hashbrown::HashMap;
+
+use crate::error::{Error, Result};
+use crate::format::FormatSpec;
+use crate::token_iterator::Token;
+
+enum Segment<'t> {
+    Text(&'t str),
+    Slot(usize, Option<FormatSpec>),
+}
+
+/// A template whose placeholder names were interned into slots, created with
+/// [`Template::compile`](crate::Template::compile).
+///
+/// Filling a [`Template`](crate::Template) looks up every placeholder in the
+/// context, so a name that appears several times is hashed once per
+/// appearance and per render. A compiled template instead looks up each
+/// unique name once per context to build a slot array, and renders from it
+/// by index. This pays off when the same template is rendered many times.
+///
+/// Example:
+/// ```rust
+/// # use text_placeholder::Template;
+/// # use std::borrow::Cow;
+/// let template = Template::new("{{name}} <{{email}}>, dear {{name}}");
+/// let compiled = template.compile();
+///
+/// assert_eq!(compiled.names(), &["name", "email"]);
+///
+/// for (name, email) in [("Ana", "ana@example.com"), ("Bo", "bo@example.com")] {
+///     let slots = compiled
+///         .slots(|key| match key {
+///             "name" => Some(Cow::Borrowed(name)),
+///             "email" => Some(Cow::Borrowed(email)),
+///             _ => None,
+///         })
+///         .unwrap();
+///
+///     assert_eq!(compiled.render(&slots), format!("{name} <{email}>, dear {name}"));
+/// }
+/// ```
+pub struct CompiledTemplate<'t> {
+    source: &'t str,
+    segments: Vec<Segment<'t>>,
+    names: Vec<&'t str>,
+    text_len: usize,
+}
+
+impl<'t> CompiledTemplate<'t> {
+    pub(crate) fn new(source: &'t str, tokens: &[Token<'t>]) -> Self {
+        let mut slots = HashMap::new();
+        let mut compiled = Self {
+            source,
+            segments: Vec::with_capacity(tokens.len()),
+            names: Vec::new(),
+            text_len: 0,
+        };
+
+        for token in tokens {
+            match token {
+                Token::Text(s) => {
+                    compiled.text_len += s.len();
+                    compiled.segments.push(Segment::Text(s));
+                }
+                Token::Placeholder(s, _, spec) => {
+                    let slot = *slots.entry(*s).or_insert_with(|| {
+                        compiled.names.push(*s);
+                        compiled.names.len() - 1
+                    });
+                    compiled.segments.push(Segment::Slot(slot, *spec));
+                }
+            }
+        }
+
+        compiled
+    }
+
+    /// Returns the unique placeholder names, the value for `names()[i]` is
+    /// expected at `slots[i]` when rendering.
+    pub fn names(&self) -> &[&'t str] {
+        &self.names
+    }
+
+    /// Builds the slot array for a context, calling `replacements` once per
+    /// unique placeholder name.
+    ///
+    /// Placeholders without an associated value (the function returns `None`)
+    /// will result in a `Error::MissingValue`.
+    pub fn slots<'a, F>(&self, mut replacements: F) -> Result<Vec<Cow<'a, str>>>
+    where
+        F: FnMut(&'t str) -> Option<Cow<'a, str>>,
+    {
+        self.names
+            .iter()
+            .map(|name| replacements(name).ok_or_else(|| Error::missing_value(self.source, name)))
+            .collect()
+    }
+
+    /// Builds the slot array for a context from the provided `replacements`
+    /// HashMap, hashing each unique placeholder name once.
+    ///
+    /// Placeholders without an associated value will be replaced with an empty string.
+    pub fn slots_from_hashmap<'a>(&self, replacements: &HashMap<&str, &'a str>) -> Vec<&'a str> {
+        self.names
+            .iter()
+            .map(|name| replacements.get(name).copied().unwrap_or(""))
+            .collect()
+    }
+
+    /// Renders the template replacing each placeholder with the value of its
+    /// slot, see [`CompiledTemplate::names`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slots` has fewer elements than [`CompiledTemplate::names`].
+    pub fn render<S: AsRef<str>>(&self, slots: &[S]) -> String {
+        assert!(
+            slots.len() >= self.names.len(),
+            "expected {} slots, got {}",
+            self.names.len(),
+            slots.len()
+        );
+
+        let mut capacity = self.text_len;
+        for segment in &self.segments {
+            if let Segment::Slot(slot, _) = segment {
+                capacity += slots[*slot].as_ref().len();
+            }
+        }
+
+        let mut result = String::with_capacity(capacity);
+
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(s) => result.push_str(s),
+                Segment::Slot(slot, None) => result.push_str(slots[*slot].as_ref()),
+                Segment::Slot(slot, Some(spec)) => spec.write(&mut result, slots[*slot].as_ref()),
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::Cow, borrow::ToOwned, string::ToString};
+
+    #[cfg(feature = "std")]
+    use std::collections::HashMap;
+
+    #[cfg(not(feature = "std"))]
+    use hashbrown::HashMap;
+
+    use crate::Template;
+
+    #[test]
+    fn test_compiled_names_are_unique() {
+        let template = Template::new("{{a}} {{b}} {{a}} {{c}} {{b}}");
+
+        assert_eq!(template.compile().names(), &["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_compiled_render_from_slices() {
+        let template = Template::new("{{a}}-{{b}}-{{a}}");
+        let compiled = template.compile();
+
+        assert_eq!(compiled.render(&["x", "y"]), "x-y-x");
+        assert_eq!(compiled.render(&["1", "2"]), "1-2-1");
+    }
+
+    #[test]
+    fn test_compiled_render_format_specifiers() {
+        let template = Template::new("{{a:>4}}|{{a:.1}}|{{b:^5}}");
+        let compiled = template.compile();
+
+        assert_eq!(compiled.names(), &["a", "b"]);
+        assert_eq!(compiled.render(&["2.25", "x"]), "2.25|2.2|  x  ");
+    }
+
+    #[test]
+    fn test_compiled_slots_call_function_once_per_name() {
+        let template = Template::new("{{a}} {{b}} {{a}} {{a}}");
+        let compiled = template.compile();
+
+        let mut calls = 0;
+        let slots = compiled
+            .slots(|key| {
+                calls += 1;
+                Some(Cow::Owned(key.to_uppercase()))
+            })
+            .unwrap();
+
+        assert_eq!(calls, 2);
+        assert_eq!(compiled.render(&slots), "A B A A");
+    }
+
+    #[test]
+    fn test_compiled_slots_missing_value() {
+        let template = Template::new("hello {{placeholder}}");
+
+        assert_eq!(
+            template.compile().slots(|_| None).map_err(|e| e.to_string()),
+            Err("Error while replacing placeholder. Reason: missing value for placeholder named 'placeholder'.".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_compiled_slots_from_hashmap() {
+        let template = Template::new("hello {{first}} {{second}}");
+        let compiled = template.compile();
+
+        let mut table = HashMap::new();
+        table.insert("first", "crazy");
+
+        let slots = compiled.slots_from_hashmap(&table);
+
+        assert_eq!(compiled.render(&slots), "hello crazy ");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 2 slots, got 1")]
+    fn test_compiled_render_missing_slots() {
+        Template::new("{{a}} {{b}}").compile().render(&["x"]);
+    }
+}
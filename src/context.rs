@@ -0,0 +1,184 @@
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(not(feature = "std"))]
+use hashbrown::HashMap;
+
+#[cfg(feature = "struct_context")]
+use serde_json::Value;
+
+/// A typed context that can be used to fill a [`Template`](crate::Template).
+///
+/// It is implemented for `HashMap` and `BTreeMap` with string keys and
+/// values. Structs usually implement it through `#[derive(PlaceholderContext)]`,
+/// which is available with the `derive` feature and generates a direct lookup
+/// of the struct fields without going through `serde_json`.
+///
+/// Example:
+/// ```rust
+/// # use text_placeholder::{PlaceholderContext, Template};
+/// # use std::borrow::Cow;
+/// struct Context {
+///     first: String,
+/// }
+///
+/// impl PlaceholderContext for Context {
+///     fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+///         match key {
+///             "first" => Some(Cow::Borrowed(&self.first)),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let context = Context { first: "text".to_string() };
+/// assert_eq!(Template::new("Hello {{first}}!").fill_with_context(&context), "Hello text!");
+/// ```
+pub trait PlaceholderContext {
+    /// Returns the value associated with the placeholder named `key`, or `None`
+    /// when the context does not provide one.
+    fn get(&self, key: &str) -> Option<Cow<'_, str>>;
+
+    /// Returns the keys the context provides values for, used to suggest
+    /// similar names when a placeholder is missing. Returns no keys by default.
+    fn keys(&self) -> Vec<&str> {
+        Vec::new()
+    }
+}
+
+impl<C: PlaceholderContext + ?Sized> PlaceholderContext for &C {
+    fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+        (**self).get(key)
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        (**self).keys()
+    }
+}
+
+impl<K, V, S> PlaceholderContext for HashMap<K, V, S>
+where
+    K: Borrow<str> + Hash + Eq,
+    V: AsRef<str>,
+    S: BuildHasher,
+{
+    fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+        HashMap::get(self, key).map(|value| Cow::Borrowed(value.as_ref()))
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        HashMap::keys(self).map(Borrow::borrow).collect()
+    }
+}
+
+impl<K, V> PlaceholderContext for BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+    V: AsRef<str>,
+{
+    fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+        BTreeMap::get(self, key).map(|value| Cow::Borrowed(value.as_ref()))
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        BTreeMap::keys(self).map(Borrow::borrow).collect()
+    }
+}
+
+/// Allows checking serialized structs with
+/// [`Template::unused_keys`](crate::Template::unused_keys) and
+/// [`Template::check_context`](crate::Template::check_context), only string
+/// values are provided.
+#[cfg(feature = "struct_context")]
+impl PlaceholderContext for Value {
+    fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+        lookup(self, key).and_then(Value::as_str).map(Cow::Borrowed)
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        match self.as_object() {
+            Some(object) => object.keys().map(|key| key.as_str()).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Returns the value of the placeholder `name` within a serialized context.
+/// Dotted names such as `user.first` are looked up in nested objects, unless
+/// the context has a key with the whole name.
+#[cfg(feature = "struct_context")]
+pub(crate) fn lookup<'v>(value: &'v Value, name: &str) -> Option<&'v Value> {
+    if let Some(value) = value.get(name) {
+        return Some(value);
+    }
+
+    if !name.contains('.') {
+        return None;
+    }
+
+    name.split('.')
+        .try_fold(value, |value, segment| value.get(segment))
+}
+
+/// A value that can replace a placeholder, used by the fields of structs
+/// deriving [`PlaceholderContext`].
+///
+/// An `Option` field that is `None` behaves like a missing value.
+pub trait PlaceholderValue {
+    fn to_placeholder(&self) -> Option<Cow<'_, str>>;
+}
+
+impl PlaceholderValue for str {
+    fn to_placeholder(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl PlaceholderValue for String {
+    fn to_placeholder(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl PlaceholderValue for Cow<'_, str> {
+    fn to_placeholder(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl<T: PlaceholderValue + ?Sized> PlaceholderValue for &T {
+    fn to_placeholder(&self) -> Option<Cow<'_, str>> {
+        (**self).to_placeholder()
+    }
+}
+
+impl<T: PlaceholderValue> PlaceholderValue for Option<T> {
+    fn to_placeholder(&self) -> Option<Cow<'_, str>> {
+        self.as_ref().and_then(PlaceholderValue::to_placeholder)
+    }
+}
+
+macro_rules! impl_placeholder_value_with_to_string {
+    ($($ty:ty),*) => {
+        $(
+            impl PlaceholderValue for $ty {
+                fn to_placeholder(&self) -> Option<Cow<'_, str>> {
+                    Some(Cow::Owned(self.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+impl_placeholder_value_with_to_string!(
+    bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
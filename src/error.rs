@@ -1,8 +1,14 @@
-use alloc::string::String;
+use alloc::{boxed::Box, string::String, string::ToString, vec::Vec};
+use core::error::Error as CoreError;
 use core::fmt;
+use core::ops::Range;
+
+use crate::suggest;
+use crate::token_iterator::span_of;
+use crate::validation::InvalidNameReason;
 
 #[cfg(feature = "std")]
-use std::error::Error as StdError;
+use std::{io, path::PathBuf};
 
 #[cfg(feature = "struct_context")]
 use serde_json::Error as SerdeJsonError;
@@ -10,10 +16,91 @@ use serde_json::Error as SerdeJsonError;
 pub type Result<T> = ::core::result::Result<T, Error>;
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
-    PlaceholderError(String),
+    /// A placeholder without an associated value found by a strict fill. The
+    /// span is the byte range of the name within the template text, and the
+    /// suggestions are similar keys found in the context, closest first.
+    MissingValue {
+        name: String,
+        span: Range<usize>,
+        suggestions: Vec<String>,
+    },
+    /// A placeholder whose value in a serialized struct is not a string. The
+    /// kind is the JSON type of the value, such as `"number"` or `"array"`.
+    #[cfg(feature = "struct_context")]
+    NonScalarValue { name: String, kind: &'static str },
+    /// A placeholder name rejected by strict parsing. The span is the byte
+    /// range of the name within the template text.
+    InvalidName {
+        name: String,
+        span: Range<usize>,
+        reason: InvalidNameReason,
+    },
+    /// A placeholder without its closing boundary found by strict parsing.
+    /// The span goes from the starting boundary to the end of the template text.
+    Unclosed { span: Range<usize> },
+    /// A set-delimiter tag with an empty or identical pair of boundaries found
+    /// by strict parsing. The span is the byte range of the tag contents.
+    InvalidDelimiters { span: Range<usize> },
+    /// Two placeholders not separated by any text, found while matching a
+    /// string against a template.
+    AdjacentPlaceholders { first: String, second: String },
+    /// Keys of a context that no placeholder refers to, found by
+    /// [`Template::check_context`](crate::Template::check_context).
+    UnusedKeys { keys: Vec<String> },
+    /// Two files loaded by [`TemplateDir`](crate::TemplateDir) with the same
+    /// name, such as `welcome.txt` and `welcome.html`.
+    #[cfg(feature = "std")]
+    DuplicateTemplate { name: String },
+    /// A file or directory that could not be read by
+    /// [`TemplateDir`](crate::TemplateDir).
+    #[cfg(feature = "std")]
+    Io { path: PathBuf, source: io::Error },
+    /// The files that could not be loaded by [`TemplateDir`](crate::TemplateDir),
+    /// with their path relative to the directory.
+    #[cfg(feature = "std")]
+    Load { errors: Vec<(PathBuf, Error)> },
+    /// An error returned by the function given to
+    /// [`Template::try_fill_with_function`](crate::Template::try_fill_with_function)
+    /// for the placeholder `name`.
+    ResolverError {
+        name: String,
+        source: Box<dyn CoreError + Send + Sync>,
+    },
     #[cfg(feature = "struct_context")]
     SerdeError(SerdeJsonError),
+    /// An error while reading a CSV file, available with the `csv` feature.
+    #[cfg(feature = "csv")]
+    Csv(csv::Error),
+}
+
+impl Error {
+    /// Builds an `Error::MissingValue` for `name`, which must be a slice of
+    /// the template text `source`.
+    pub(crate) fn missing_value(source: &str, name: &str) -> Self {
+        Error::MissingValue {
+            name: name.to_string(),
+            span: span_of(source, name),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Attaches the `keys` similar to the name of an `Error::MissingValue`,
+    /// other errors are returned unchanged.
+    pub(crate) fn with_suggestions<'k, I>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = &'k str>,
+    {
+        if let Error::MissingValue {
+            name, suggestions, ..
+        } = &mut self
+        {
+            *suggestions = suggest::suggestions(name, keys);
+        }
+
+        self
+    }
 }
 
 #[cfg(feature = "struct_context")]
@@ -23,40 +110,164 @@ impl From<SerdeJsonError> for Error {
     }
 }
 
+#[cfg(feature = "csv")]
+impl From<csv::Error> for Error {
+    fn from(err: csv::Error) -> Error {
+        Error::Csv(err)
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Error::PlaceholderError(msg) => {
-                write!(f, "Error while replacing placeholder. Reason: {}", msg)
+            Error::MissingValue {
+                name, suggestions, ..
+            } => {
+                write!(
+                    f,
+                    "Error while replacing placeholder. Reason: missing value for placeholder named '{}'",
+                    name
+                )?;
+
+                match suggestions.split_last() {
+                    None => f.write_str("."),
+                    Some((last, [])) => write!(f, ", did you mean '{}'?", last),
+                    Some((last, rest)) => {
+                        f.write_str(", did you mean ")?;
+                        for (i, suggestion) in rest.iter().enumerate() {
+                            if i > 0 {
+                                f.write_str(", ")?;
+                            }
+                            write!(f, "'{}'", suggestion)?;
+                        }
+                        write!(f, " or '{}'?", last)
+                    }
+                }
+            }
+            #[cfg(feature = "struct_context")]
+            Error::NonScalarValue { name, kind } => write!(
+                f,
+                "Error while replacing placeholder. Reason: value of type {} for placeholder named '{}' cannot be converted to a string.",
+                kind, name
+            ),
+            Error::InvalidName { name, span, reason } => write!(
+                f,
+                "Error while parsing template. Reason: invalid placeholder name '{}' at {}..{}, {}.",
+                name, span.start, span.end, reason
+            ),
+            Error::Unclosed { span } => write!(
+                f,
+                "Error while parsing template. Reason: unclosed placeholder at {}..{}.",
+                span.start, span.end
+            ),
+            Error::InvalidDelimiters { span } => write!(
+                f,
+                "Error while parsing template. Reason: invalid delimiters at {}..{}.",
+                span.start, span.end
+            ),
+            Error::AdjacentPlaceholders { first, second } => write!(
+                f,
+                "Error while matching template. Reason: placeholders '{}' and '{}' are not separated by any text.",
+                first, second
+            ),
+            Error::UnusedKeys { keys } => {
+                f.write_str("Error while checking context. Reason: keys ")?;
+                for (i, key) in keys.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "'{}'", key)?;
+                }
+                f.write_str(" are not used by any placeholder.")
+            }
+            #[cfg(feature = "std")]
+            Error::DuplicateTemplate { name } => write!(
+                f,
+                "Error while loading templates. Reason: more than one file is named '{}'.",
+                name
+            ),
+            #[cfg(feature = "std")]
+            Error::Io { path, source } => write!(
+                f,
+                "Error while reading '{}'. Reason: {}",
+                path.display(),
+                source
+            ),
+            #[cfg(feature = "std")]
+            Error::Load { errors } => {
+                write!(
+                    f,
+                    "Error while loading templates. Reason: {} file(s) could not be loaded.",
+                    errors.len()
+                )?;
+                for (path, err) in errors {
+                    write!(f, "\n  {}: {}", path.display(), err)?;
+                }
+                Ok(())
             }
+            Error::ResolverError { name, source } => write!(
+                f,
+                "Error while replacing placeholder named '{}'. Reason: {}",
+                name, source
+            ),
             #[cfg(feature = "struct_context")]
             Error::SerdeError(err) => write!(
                 f,
                 "Error while converting the context to a serde_json::Value. Error: {}",
                 err
             ),
+            #[cfg(feature = "csv")]
+            Error::Csv(err) => write!(f, "Error while reading CSV. Reason: {}", err),
         }
     }
 }
 
-// Only available when the standard library is available
-// Enable for no_std when Error trait is stabilized for no_std
-// See: https://github.com/rust-lang/rust/issues/103765
-#[cfg(feature = "std")]
-impl StdError for Error {
+impl CoreError for Error {
     fn description(&self) -> &str {
         match self {
-            Error::PlaceholderError(_) => "PlaceholderError",
+            Error::MissingValue { .. } => "MissingValue",
+            #[cfg(feature = "struct_context")]
+            Error::NonScalarValue { .. } => "NonScalarValue",
+            Error::InvalidName { .. } => "InvalidName",
+            Error::Unclosed { .. } => "Unclosed",
+            Error::InvalidDelimiters { .. } => "InvalidDelimiters",
+            Error::AdjacentPlaceholders { .. } => "AdjacentPlaceholders",
+            Error::UnusedKeys { .. } => "UnusedKeys",
+            #[cfg(feature = "std")]
+            Error::DuplicateTemplate { .. } => "DuplicateTemplate",
+            #[cfg(feature = "std")]
+            Error::Io { .. } => "Io",
+            #[cfg(feature = "std")]
+            Error::Load { .. } => "Load",
+            Error::ResolverError { .. } => "ResolverError",
             #[cfg(feature = "struct_context")]
             Error::SerdeError(_) => "SerdeError",
+            #[cfg(feature = "csv")]
+            Error::Csv(_) => "Csv",
         }
     }
 
-    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+    fn source(&self) -> Option<&(dyn CoreError + 'static)> {
         match self {
-            Error::PlaceholderError(_) => None,
+            Error::MissingValue { .. } => None,
+            #[cfg(feature = "struct_context")]
+            Error::NonScalarValue { .. } => None,
+            Error::InvalidName { .. } => None,
+            Error::Unclosed { .. } => None,
+            Error::InvalidDelimiters { .. } => None,
+            Error::AdjacentPlaceholders { .. } => None,
+            Error::UnusedKeys { .. } => None,
+            #[cfg(feature = "std")]
+            Error::DuplicateTemplate { .. } => None,
+            #[cfg(feature = "std")]
+            Error::Io { source, .. } => Some(source),
+            #[cfg(feature = "std")]
+            Error::Load { .. } => None,
+            Error::ResolverError { source, .. } => Some(source.as_ref()),
             #[cfg(feature = "struct_context")]
             Error::SerdeError(ref e) => Some(e),
+            #[cfg(feature = "csv")]
+            Error::Csv(ref e) => Some(e),
         }
     }
 }
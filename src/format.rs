@@ -0,0 +1,621 @@
+use alloc::{borrow::Cow, string::String};
+use core::fmt;
+
+#[cfg(feature = "locale")]
+use crate::locale::Locale;
+
+/// Separator between a placeholder name and its format spec, such as
+/// `{{price:>10.2}}`.
+const FORMAT_SEPARATOR: char = ':';
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+impl Align {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '<' => Some(Align::Left),
+            '^' => Some(Align::Center),
+            '>' => Some(Align::Right),
+            _ => None,
+        }
+    }
+
+    fn as_char(self) -> char {
+        match self {
+            Align::Left => '<',
+            Align::Center => '^',
+            Align::Right => '>',
+        }
+    }
+}
+
+/// A locale style of a format spec, such as `{{total:currency}}`, formatting
+/// values with the locale of the template.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum Style {
+    Decimal,
+    Currency,
+    Percent,
+    DateShort,
+    DateMedium,
+    DateLong,
+}
+
+impl Style {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "decimal" => Some(Style::Decimal),
+            "currency" => Some(Style::Currency),
+            "percent" => Some(Style::Percent),
+            "date-short" => Some(Style::DateShort),
+            "date-medium" => Some(Style::DateMedium),
+            "date-long" => Some(Style::DateLong),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Style::Decimal => "decimal",
+            Style::Currency => "currency",
+            Style::Percent => "percent",
+            Style::DateShort => "date-short",
+            Style::DateMedium => "date-medium",
+            Style::DateLong => "date-long",
+        }
+    }
+
+    /// Returns whether the style formats numbers, which take a precision,
+    /// their number of decimals, and are aligned to the right by default.
+    fn is_numeric(self) -> bool {
+        matches!(self, Style::Decimal | Style::Currency | Style::Percent)
+    }
+}
+
+/// A format spec following the placeholder name, with the same syntax and
+/// meaning as in `core::fmt`: `[[fill]align][sign]['0'][width]['.' precision]`.
+///
+/// Values are formatted as text like `&str` values: they are aligned to the
+/// left by default and the precision is their maximum number of characters.
+///
+/// Numbers are aligned to the right by default, honor the `+` sign and `0`
+/// flags, and the precision is their number of decimals. The numbers of
+/// structs filled with `fill_with_struct` are always formatted as numbers.
+/// Other values are text, including the numeric fields of structs deriving
+/// `PlaceholderContext`, and text that looks like a number is only formatted
+/// as a number by specs with a precision, a sign or the `0` flag, such as
+/// `{{price:.2}}`, so `{{id:6}}` pads `123` like `format!("{:6}", "123")`
+/// does.
+///
+/// A locale style can follow the width: `[[fill]align][width]style['.'
+/// precision]`, such as `{{total:>12currency}}`. With the `locale` feature
+/// the value is formatted with the locale of the template, see
+/// `Template::with_locale`, and without it the value is written as is.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FormatSpec {
+    fill: char,
+    align: Option<Align>,
+    plus: bool,
+    zero: bool,
+    width: Option<usize>,
+    precision: Option<usize>,
+    style: Option<Style>,
+    /// Locale of the template, the fallback locale when `None`.
+    #[cfg(feature = "locale")]
+    locale: Option<&'static Locale>,
+}
+
+impl Default for FormatSpec {
+    fn default() -> Self {
+        Self {
+            fill: ' ',
+            align: None,
+            plus: false,
+            zero: false,
+            width: None,
+            precision: None,
+            style: None,
+            #[cfg(feature = "locale")]
+            locale: None,
+        }
+    }
+}
+
+/// A value replacing a placeholder.
+pub(crate) enum Replacement<'a> {
+    Text(Cow<'a, str>),
+    /// A number of a typed context, such as a `serde_json::Number`.
+    #[cfg(feature = "struct_context")]
+    Number(String),
+}
+
+impl Replacement<'_> {
+    /// Appends the value formatted according to `spec` to `result`.
+    pub(crate) fn write(&self, result: &mut String, spec: Option<&FormatSpec>) {
+        match (self, spec) {
+            (Replacement::Text(value), None) => result.push_str(value),
+            (Replacement::Text(value), Some(spec)) => spec.write(result, value),
+            #[cfg(feature = "struct_context")]
+            (Replacement::Number(value), None) => result.push_str(value),
+            #[cfg(feature = "struct_context")]
+            (Replacement::Number(value), Some(spec)) => spec.write_number(result, value),
+        }
+    }
+}
+
+/// Splits the contents of a tag into the placeholder name and its format
+/// spec. The name is kept whole when no colon is followed by a valid spec,
+/// so names such as `ns:key` are still supported, and a trailing colon keeps
+/// names such as `a:5` whole: `{{a:5:}}` is the placeholder `a:5`.
+pub(crate) fn split(tag: &str) -> (&str, Option<FormatSpec>) {
+    for (i, _) in tag.match_indices(FORMAT_SEPARATOR) {
+        let name = tag[..i].trim_end();
+
+        if name.is_empty() {
+            continue;
+        }
+
+        // Leading whitespace is ignored like around names, a space being
+        // the default fill character anyway.
+        let spec = tag[i + FORMAT_SEPARATOR.len_utf8()..].trim_start();
+
+        if let Some(spec) = FormatSpec::parse(spec) {
+            return (name, Some(spec));
+        }
+    }
+
+    (tag, None)
+}
+
+impl FormatSpec {
+    /// Parses a spec, returns `None` when it is not valid.
+    pub(crate) fn parse(spec: &str) -> Option<Self> {
+        let mut format = Self::default();
+        let mut rest = spec;
+
+        let mut chars = rest.chars();
+        let first = chars.next();
+        match (first, chars.next().and_then(Align::from_char)) {
+            (Some(fill), Some(align)) => {
+                format.fill = fill;
+                format.align = Some(align);
+                rest = &rest[fill.len_utf8() + 1..];
+            }
+            _ => {
+                if let Some(align) = first.and_then(Align::from_char) {
+                    format.align = Some(align);
+                    rest = &rest[1..];
+                }
+            }
+        }
+
+        if let Some(r) = rest.strip_prefix('+') {
+            format.plus = true;
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix('-') {
+            // Accepted by `core::fmt` but without any effect.
+            rest = r;
+        }
+
+        if let Some(r) = rest.strip_prefix('0') {
+            format.zero = true;
+            rest = r;
+        }
+
+        let (width, r) = parse_count(rest)?;
+        format.width = width;
+        rest = r;
+
+        // Styles are parsed regardless of the `locale` feature, so that
+        // enabling it does not change the names of placeholders.
+        let end = rest
+            .find(|c: char| !c.is_ascii_lowercase() && c != '-')
+            .unwrap_or(rest.len());
+
+        if end > 0 {
+            let style = Style::from_name(&rest[..end])?;
+            // Signs and zeros are written by the locale.
+            if format.plus || format.zero || (!style.is_numeric() && rest[end..].starts_with('.')) {
+                return None;
+            }
+            format.style = Some(style);
+            rest = &rest[end..];
+        }
+
+        if let Some(r) = rest.strip_prefix('.') {
+            let (precision, r) = parse_count(r)?;
+            format.precision = Some(precision?);
+            rest = r;
+        }
+
+        rest.is_empty().then_some(format)
+    }
+
+    #[cfg(feature = "locale")]
+    /// Sets the locale used by the locale style of the spec.
+    pub(crate) fn set_locale(&mut self, locale: &'static Locale) {
+        self.locale = Some(locale);
+    }
+
+    /// Returns whether the spec affects the value, which is not the case of
+    /// an empty spec such as `{{name:}}`.
+    fn is_noop(&self) -> bool {
+        self.width.is_none() && self.precision.is_none() && !self.plus && self.style.is_none()
+    }
+
+    /// Returns whether the spec only applies to numbers, so that text that
+    /// looks like a number is formatted as a number.
+    pub(crate) fn is_numeric(&self) -> bool {
+        if let Some(style) = self.style {
+            return style.is_numeric();
+        }
+
+        self.precision.is_some() || self.plus || self.zero
+    }
+
+    /// Appends `value` formatted according to the spec to `result`.
+    pub(crate) fn write(&self, result: &mut String, value: &str) {
+        if let Some(style) = self.style {
+            return self.write_style(result, value, style);
+        }
+
+        match parse_number(value) {
+            Some(number) if self.is_numeric() => self.format_number(result, value, number),
+            _ if self.is_noop() => result.push_str(value),
+            _ => self.write_text(result, value),
+        }
+    }
+
+    #[cfg(feature = "struct_context")]
+    /// Appends `value`, which is known to be a number, formatted according to
+    /// the spec to `result`.
+    pub(crate) fn write_number(&self, result: &mut String, value: &str) {
+        if let Some(style) = self.style {
+            return self.write_style(result, value, style);
+        }
+
+        match parse_number(value) {
+            Some(number) if !self.is_noop() => self.format_number(result, value, number),
+            _ => self.write(result, value),
+        }
+    }
+
+    /// Appends `value` formatted with the locale of the template, or as is
+    /// when it is not a number or a date, padded up to the width.
+    fn write_style(&self, result: &mut String, value: &str, style: Style) {
+        match self.localize(style, value) {
+            Some(text) => {
+                let align = if style.is_numeric() {
+                    Align::Right
+                } else {
+                    Align::Left
+                };
+                self.pad(result, &[&text], text.chars().count(), align);
+            }
+            None => self.pad(result, &[value], value.chars().count(), Align::Left),
+        }
+    }
+
+    #[cfg(feature = "locale")]
+    fn localize(&self, style: Style, value: &str) -> Option<String> {
+        let locale = self.locale.unwrap_or_else(Locale::fallback);
+        style.format(locale, value, self.precision)
+    }
+
+    /// Values are written as is without the `locale` feature.
+    #[cfg(not(feature = "locale"))]
+    fn localize(&self, _: Style, _: &str) -> Option<String> {
+        None
+    }
+
+    fn format_number(&self, result: &mut String, value: &str, number: f64) {
+        let text = match self.precision {
+            None => Cow::Borrowed(value),
+            // Integers are extended rather than converted in order to keep
+            // the digits that do not fit in a `f64`.
+            Some(0) if is_integer(value) => Cow::Borrowed(value),
+            Some(precision) if is_integer(value) => {
+                Cow::Owned(format!("{}.{:0<2$}", value, "", precision))
+            }
+            Some(precision) => Cow::Owned(format!("{:.*}", precision, number)),
+        };
+
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(&text)),
+        };
+        let sign = match (negative, self.plus) {
+            (true, _) => "-",
+            (false, true) => "+",
+            (false, false) => "",
+        };
+
+        let len = sign.len() + digits.chars().count();
+        match self.width {
+            // The zero flag pads after the sign and overrides the fill and
+            // the alignment.
+            Some(width) if self.zero && width > len => {
+                result.push_str(sign);
+                result.extend(core::iter::repeat('0').take(width - len));
+                result.push_str(digits);
+            }
+            _ => self.pad(result, &[sign, digits], len, Align::Right),
+        }
+    }
+
+    fn write_text(&self, result: &mut String, value: &str) {
+        let text = match self.precision.and_then(|p| value.char_indices().nth(p)) {
+            Some((i, _)) => &value[..i],
+            None => value,
+        };
+
+        self.pad(result, &[text], text.chars().count(), Align::Left);
+    }
+
+    /// Appends `parts`, which are `len` characters long, padded with the fill
+    /// character up to the width.
+    fn pad(&self, result: &mut String, parts: &[&str], len: usize, default: Align) {
+        let padding = self.width.unwrap_or(0).saturating_sub(len);
+        let (before, after) = match self.align.unwrap_or(default) {
+            Align::Left => (0, padding),
+            Align::Center => (padding / 2, padding.div_ceil(2)),
+            Align::Right => (padding, 0),
+        };
+
+        result.extend(core::iter::repeat(self.fill).take(before));
+        for part in parts {
+            result.push_str(part);
+        }
+        result.extend(core::iter::repeat(self.fill).take(after));
+    }
+}
+
+impl fmt::Display for FormatSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(align) = self.align {
+            if self.fill != ' ' {
+                write!(f, "{}", self.fill)?;
+            }
+            write!(f, "{}", align.as_char())?;
+        }
+        if self.plus {
+            f.write_str("+")?;
+        }
+        if self.zero {
+            f.write_str("0")?;
+        }
+        if let Some(width) = self.width {
+            write!(f, "{}", width)?;
+        }
+        if let Some(style) = self.style {
+            f.write_str(style.name())?;
+        }
+        if let Some(precision) = self.precision {
+            write!(f, ".{}", precision)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the leading digits of `s`. Returns `None` when they overflow, and
+/// no count when there are no digits.
+fn parse_count(s: &str) -> Option<(Option<usize>, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+
+    if end == 0 {
+        return Some((None, s));
+    }
+
+    Some((Some(s[..end].parse().ok()?), &s[end..]))
+}
+
+/// Parses values that are written as a decimal number, such as `-12`, `3.5`
+/// or `1e3`. Words accepted by `f64::from_str` such as `inf` are text.
+pub(crate) fn parse_number(value: &str) -> Option<f64> {
+    let bytes = value.as_bytes();
+
+    if !bytes.iter().any(u8::is_ascii_digit)
+        || !bytes
+            .iter()
+            .all(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.' | b'e' | b'E'))
+    {
+        return None;
+    }
+
+    value.parse().ok()
+}
+
+pub(crate) fn is_integer(value: &str) -> bool {
+    let digits = value
+        .strip_prefix(|c| c == '-' || c == '+')
+        .unwrap_or(value);
+
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use super::{parse_number, split, FormatSpec, Style};
+
+    fn format(spec: &str, value: &str) -> String {
+        let mut result = String::new();
+        FormatSpec::parse(spec).unwrap().write(&mut result, value);
+        result
+    }
+
+    fn format_number(spec: &str, value: &str) -> String {
+        let mut result = String::new();
+        FormatSpec::parse(spec).unwrap().format_number(
+            &mut result,
+            value,
+            parse_number(value).unwrap(),
+        );
+        result
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(split("price:>10.2").0, "price");
+        assert_eq!(split("price : >10").0, "price");
+        assert_eq!(split("price:").0, "price");
+        assert_eq!(split("price"), ("price", None));
+        assert_eq!(split("ns:key"), ("ns:key", None));
+        assert_eq!(split("ns:key:>5").0, "ns:key");
+        assert_eq!(split("x::^5").0, "x");
+        assert_eq!(split(":>5"), (":>5", None));
+    }
+
+    #[test]
+    fn test_split_trailing_colon_keeps_name() {
+        assert_eq!(split("a:5").0, "a");
+        assert_eq!(split("a:5:"), ("a:5", FormatSpec::parse("")));
+        assert_eq!(split("ns:").0, "ns");
+        assert_eq!(split("ns::"), ("ns:", FormatSpec::parse("")));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for spec in [
+            "x",
+            ">>>",
+            "10x",
+            ".",
+            ".x",
+            "#10",
+            "99999999999999999999999",
+        ] {
+            assert_eq!(FormatSpec::parse(spec), None, "{}", spec);
+        }
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for spec in ["", ">10.2", "*^+08", "<5", ".3", "+"] {
+            assert_eq!(FormatSpec::parse(spec).unwrap().to_string(), spec);
+        }
+    }
+
+    #[test]
+    fn test_numbers_match_core_fmt() {
+        let cases: [(&str, &str, String); 10] = [
+            (">10.2", "1234.5678", format!("{:>10.2}", 1234.5678)),
+            ("10.2", "12.3456", format!("{:10.2}", 12.3456)),
+            ("<10", "42", format!("{:<10}", 42)),
+            ("^9", "42", format!("{:^9}", 42)),
+            ("*^9", "-42", format!("{:*^9}", -42)),
+            ("+", "42", format!("{:+}", 42)),
+            ("+.1", "-0.25", format!("{:+.1}", -0.25)),
+            ("08.2", "-3.5", format!("{:08.2}", -3.5)),
+            ("+06", "7", format!("{:+06}", 7)),
+            (".0", "2.5", format!("{:.0}", 2.5)),
+        ];
+
+        for (spec, value, expected) in cases {
+            assert_eq!(format_number(spec, value), expected, "{} {}", spec, value);
+        }
+    }
+
+    #[test]
+    fn test_text_looking_like_numbers() {
+        // Formatted as text unless the spec only applies to numbers.
+        assert_eq!(format("6", "123"), format!("{:6}", "123"));
+        assert_eq!(format("^7", "-4.5"), format!("{:^7}", "-4.5"));
+        assert_eq!(format("6.2", "3.5"), "  3.50");
+        assert_eq!(format("+", "42"), "+42");
+        assert_eq!(format("05", "7"), "00007");
+        assert_eq!(format_number("6", "123"), format!("{:6}", 123));
+    }
+
+    #[test]
+    fn test_integer_precision_keeps_digits() {
+        assert_eq!(format(".2", "5"), "5.00");
+        assert_eq!(
+            format(".2", "123456789012345678901"),
+            "123456789012345678901.00"
+        );
+        assert_eq!(format(".0", "-5"), "-5");
+    }
+
+    #[test]
+    fn test_text_match_core_fmt() {
+        let cases: [(&str, &str, String); 6] = [
+            ("10", "ana", format!("{:10}", "ana")),
+            (">10", "ana", format!("{:>10}", "ana")),
+            ("-^7", "ana", format!("{:-^7}", "ana")),
+            (".2", "héllo", format!("{:.2}", "héllo")),
+            ("5.2", "héllo", format!("{:5.2}", "héllo")),
+            ("05", "ab", format!("{:05}", "ab")),
+        ];
+
+        for (spec, value, expected) in cases {
+            assert_eq!(format(spec, value), expected, "{} {}", spec, value);
+        }
+    }
+
+    #[test]
+    fn test_words_are_text() {
+        assert_eq!(format(">5", "inf"), "  inf");
+        assert_eq!(format("+", "inf"), "inf");
+        assert_eq!(format("+", "2024-01-01"), "2024-01-01");
+    }
+
+    #[test]
+    fn test_parse_locale_styles() {
+        for spec in [
+            "currency",
+            ">12currency.0",
+            "*^9percent.1",
+            "date-long",
+            "10decimal",
+        ] {
+            assert_eq!(FormatSpec::parse(spec).unwrap().to_string(), spec);
+        }
+        for spec in [
+            "money",
+            "+currency",
+            "010decimal",
+            "date-long.2",
+            "currency>5",
+        ] {
+            assert_eq!(FormatSpec::parse(spec), None, "{}", spec);
+        }
+        assert_eq!(split("total:currency").0, "total");
+        assert_eq!(split("total:currency:").0, "total:currency");
+    }
+
+    #[test]
+    fn test_style_names() {
+        for name in [
+            "decimal",
+            "currency",
+            "percent",
+            "date-short",
+            "date-medium",
+            "date-long",
+        ] {
+            assert_eq!(Style::from_name(name).unwrap().name(), name);
+        }
+        assert!(Style::from_name("date").is_none());
+    }
+
+    #[cfg(not(feature = "locale"))]
+    #[test]
+    fn test_styles_without_locale_write_values_as_is() {
+        assert_eq!(format("currency", "1234.5"), "1234.5");
+        assert_eq!(format(">8currency", "1234.5"), "  1234.5");
+        assert_eq!(format("date-long", "2024-03-05"), "2024-03-05");
+    }
+
+    #[test]
+    fn test_empty_spec() {
+        assert_eq!(format("", " ana "), " ana ");
+    }
+}
@@ -0,0 +1,105 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+#[cfg(not(feature = "std"))]
+use hashbrown::HashSet;
+
+use crate::Template;
+
+/// Placeholders and keys that do not match between a template and a set of
+/// keys, returned by [`Template::validate`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyReport<'t, 'k> {
+    /// Placeholders without a key, in order of first appearance.
+    pub missing: Vec<&'t str>,
+    /// Keys that no placeholder refers to, in the order they were given.
+    pub extra: Vec<&'k str>,
+}
+
+impl KeyReport<'_, '_> {
+    /// Returns `true` when every placeholder has a key and every key is used.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
+impl<'t> Template<'t> {
+    /// Checks the template against a set of `keys`, such as the fields of a
+    /// schema, without rendering it. Reports the placeholders that would be
+    /// missing from a context with those keys and the keys that would be
+    /// unused.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// let template = Template::new("Hello {{first}} {{last}}!");
+    ///
+    /// let report = template.validate(["first", "middle"]);
+    ///
+    /// assert!(!report.is_ok());
+    /// assert_eq!(report.missing, vec!["last"]);
+    /// assert_eq!(report.extra, vec!["middle"]);
+    ///
+    /// assert!(template.validate(["first", "last"]).is_ok());
+    /// ```
+    pub fn validate<'k, I>(&self, keys: I) -> KeyReport<'t, 'k>
+    where
+        I: IntoIterator<Item = &'k str>,
+    {
+        let placeholders = self.placeholders();
+        let used: HashSet<&str> = placeholders.iter().copied().collect();
+
+        let mut seen = HashSet::new();
+        let extra = keys
+            .into_iter()
+            .filter(|key| seen.insert(*key) && !used.contains(key))
+            .collect();
+
+        let missing = placeholders
+            .into_iter()
+            .filter(|placeholder| !seen.contains(placeholder))
+            .collect();
+
+        KeyReport { missing, extra }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::KeyReport;
+    use crate::Template;
+
+    #[test]
+    fn test_validate() {
+        let template = Template::new("{{a}} {{b}} {{a}} {{c}} {{! d }}");
+
+        assert_eq!(
+            template.validate(["d", "b", "e", "b"]),
+            KeyReport {
+                missing: vec!["a", "c"],
+                extra: vec!["d", "e"],
+            }
+        );
+    }
+
+    #[test]
+    fn test_validate_ok() {
+        let template = Template::new("{{a}} {{b}}");
+
+        assert!(template.validate(["b", "a"]).is_ok());
+        assert!(Template::new("text").validate(Vec::new()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_without_keys() {
+        let template = Template::new("{{a}} {{b}}");
+        let report = template.validate(Vec::new());
+
+        assert_eq!(report.missing, ["a", "b"]);
+        assert!(report.extra.is_empty());
+    }
+}
@@ -8,7 +8,9 @@
 //! but can be overriden with specific boundaries.
 //!
 //! This library supports passing a `HashMap` or `Struct` as a context
-//! in order to replace the specified placeholders.
+//! in order to replace the specified placeholders. Structs can either be
+//! serialized with `serde` (`struct_context` feature) or implement
+//! [`PlaceholderContext`], which can be derived with the `derive` feature.
 //!
 //! ## Example
 //!
@@ -35,27 +37,93 @@
 
 use alloc::borrow::Cow;
 
+mod format;
+mod scan;
+mod suggest;
 mod token_iterator;
+use format::{FormatSpec, Replacement};
 use token_iterator::{Token, TokenIterator};
 
 mod error;
 pub use error::{Error, Result};
 
+mod context;
+pub use context::{PlaceholderContext, PlaceholderValue};
+
+mod compiled;
+pub use compiled::CompiledTemplate;
+
+mod key_report;
+pub use key_report::KeyReport;
+
+mod owned;
+pub use owned::OwnedTemplate;
+
+#[cfg(feature = "std")]
+mod loader;
+#[cfg(feature = "std")]
+pub use loader::TemplateDir;
+
+#[cfg(feature = "std")]
+mod store;
+#[cfg(feature = "std")]
+pub use store::TemplateStore;
+
+#[cfg(feature = "csv")]
+mod merge;
+#[cfg(feature = "csv")]
+pub use merge::{CsvRow, CsvRows, MergedDocument};
+
+#[cfg(feature = "locale")]
+mod locale;
+#[cfg(feature = "locale")]
+pub use locale::{DateStyle, Locale};
+
+mod async_fill;
+mod batch;
+mod matcher;
+#[cfg(feature = "struct_context")]
+mod schema;
+mod unused;
+
+mod validation;
+pub use validation::{InvalidNameReason, NameRules};
+
+#[cfg(feature = "derive")]
+pub use text_placeholder_derive::PlaceholderContext;
+
+// Allows the code generated by the derive macro to refer to this crate by name
+// within its own tests.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as text_placeholder;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::borrow::Cow;
+    pub use alloc::vec::Vec;
+}
+
 #[cfg(feature = "struct_context")]
 extern crate serde_json;
 #[cfg(feature = "struct_context")]
 use serde::Serialize;
+#[cfg(feature = "struct_context")]
+use serde_json::Value;
 
 #[cfg(feature = "std")]
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[cfg(not(feature = "std"))]
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 
 #[macro_use]
 extern crate alloc;
 
-use alloc::{string::String, vec::Vec};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
 
 const DEFAULT_START_PLACEHOLDER: &str = "{{";
 const DEFAULT_END_PLACEHOLDER: &str = "}}";
@@ -63,7 +131,11 @@ const DEFAULT_END_PLACEHOLDER: &str = "}}";
 /// A template is composed of tokens, which in turn can represent plain text
 /// or a named placeholder.
 pub struct Template<'t> {
+    /// The text the tokens were parsed from.
+    source: &'t str,
     tokens: Vec<Token<'t>>,
+    /// Pairs of boundaries that produced the placeholder tokens.
+    delimiters: Vec<(&'t str, &'t str)>,
 }
 
 impl<'t> Template<'t> {
@@ -77,10 +149,11 @@ impl<'t> Template<'t> {
     /// let template = Template::new("Hello {{key}}!");
     /// ```
     pub fn new(text: &'t str) -> Self {
-        Self {
-            tokens: TokenIterator::new(text, DEFAULT_START_PLACEHOLDER, DEFAULT_END_PLACEHOLDER)
-                .collect(),
-        }
+        Self::from_iter(TokenIterator::new(
+            text,
+            DEFAULT_START_PLACEHOLDER,
+            DEFAULT_END_PLACEHOLDER,
+        ))
     }
 
     /// Generates a Template with boundaries specified by the `start` and `end`
@@ -92,9 +165,171 @@ impl<'t> Template<'t> {
     /// let template = Template::new_with_placeholder("Hello [key]!", "[", "]");
     /// ```
     pub fn new_with_placeholder(text: &'t str, start: &'t str, end: &'t str) -> Self {
+        Self::from_iter(TokenIterator::new(text, start, end))
+    }
+
+    /// Generates a Template recognizing every pair of boundaries specified by
+    /// the `delimiters` argument, whichever starting boundary appears first
+    /// opens the next placeholder.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// let template = Template::new_with_placeholders("Hello ${first} {{second}}!", &[("${", "}"), ("{{", "}}")]);
+    ///
+    /// assert_eq!(template.placeholders(), vec!["first", "second"]);
+    /// ```
+    pub fn new_with_placeholders(text: &'t str, delimiters: &[(&'t str, &'t str)]) -> Self {
+        Self::from_iter(TokenIterator::with_delimiters(text, delimiters))
+    }
+
+    fn from_iter(mut iter: TokenIterator<'t>) -> Self {
+        let tokens = iter.by_ref().collect();
+
         Self {
-            tokens: TokenIterator::new(text, start, end).collect(),
+            source: iter.source(),
+            tokens,
+            delimiters: iter.delimiters().to_vec(),
+        }
+    }
+
+    fn from_iter_strict(mut iter: TokenIterator<'t>) -> Result<Self> {
+        let tokens = iter.by_ref().collect();
+
+        match iter.take_error() {
+            Some(err) => Err(err),
+            None => Ok(Self {
+                source: iter.source(),
+                tokens,
+                delimiters: iter.delimiters().to_vec(),
+            }),
+        }
+    }
+
+    /// Generates a Template with boundaries specified by the handlebars syntax,
+    /// parsing it strictly with the default [`NameRules`].
+    ///
+    /// Placeholders without a closing boundary result in an `Error::Unclosed`
+    /// and invalid placeholder names result in an `Error::InvalidName`.
+    ///
+    /// For a version that does not generate an error see [`Template::new`].
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// assert!(Template::new_strict("Hello {{key}}!").is_ok());
+    /// assert!(Template::new_strict("Hello {{key!").is_err());
+    /// ```
+    pub fn new_strict(text: &'t str) -> Result<Self> {
+        Self::new_with_rules(
+            text,
+            DEFAULT_START_PLACEHOLDER,
+            DEFAULT_END_PLACEHOLDER,
+            NameRules::default(),
+        )
+    }
+
+    /// Generates a Template with boundaries specified by the `start` and `end`
+    /// arguments, parsing it strictly with the default [`NameRules`].
+    ///
+    /// For a version that does not generate an error see
+    /// [`Template::new_with_placeholder`].
+    pub fn new_with_placeholder_strict(
+        text: &'t str,
+        start: &'t str,
+        end: &'t str,
+    ) -> Result<Self> {
+        Self::new_with_rules(text, start, end, NameRules::default())
+    }
+
+    /// Generates a Template recognizing every pair of boundaries specified by
+    /// the `delimiters` argument, parsing it strictly with the default [`NameRules`].
+    ///
+    /// For a version that does not generate an error see
+    /// [`Template::new_with_placeholders`].
+    pub fn new_with_placeholders_strict(
+        text: &'t str,
+        delimiters: &[(&'t str, &'t str)],
+    ) -> Result<Self> {
+        Self::from_iter_strict(
+            TokenIterator::with_delimiters(text, delimiters).strict(NameRules::default()),
+        )
+    }
+
+    /// Generates a Template with boundaries specified by the `start` and `end`
+    /// arguments, parsing it strictly and validating placeholder names with
+    /// the provided `rules`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::{NameRules, Template};
+    /// let rules = NameRules { identifier: true, ..NameRules::default() };
+    ///
+    /// assert!(Template::new_with_rules("Hello {{first_name}}!", "{{", "}}", rules).is_ok());
+    /// assert!(Template::new_with_rules("Hello {{first name}}!", "{{", "}}", rules).is_err());
+    /// ```
+    pub fn new_with_rules(
+        text: &'t str,
+        start: &'t str,
+        end: &'t str,
+        rules: NameRules,
+    ) -> Result<Self> {
+        Self::from_iter_strict(TokenIterator::new(text, start, end).strict(rules))
+    }
+
+    #[cfg(feature = "locale")]
+    /// Sets the locale of the locale styles of the template's format specs,
+    /// such as `{{total:currency}}` or `{{day:date-long}}`. Templates without
+    /// a locale format those values in `en-US`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::{Locale, Template};
+    /// # use std::collections::HashMap;
+    /// let template = Template::new("{{total:currency}} ({{day:date-long}})")
+    ///     .with_locale(Locale::get("de-DE").unwrap());
+    ///
+    /// let table = HashMap::from([("total", "1234.56"), ("day", "2024-03-05")]);
+    ///
+    /// assert_eq!(template.fill_with_hashmap(&table), "1.234,56\u{a0}€ (5. März 2024)");
+    /// ```
+    pub fn with_locale(mut self, locale: &'static Locale) -> Self {
+        for token in &mut self.tokens {
+            if let Token::Placeholder(_, _, Some(spec)) = token {
+                spec.set_locale(locale);
+            }
         }
+
+        self
+    }
+
+    /// Returns the names of the template's placeholders in order of first
+    /// appearance, without duplicates. Comments are not included.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// let template = Template::new("{{! greeting }}Hello {{first}} {{second}} {{first}}!");
+    ///
+    /// assert_eq!(template.placeholders(), vec!["first", "second"]);
+    /// ```
+    pub fn placeholders(&self) -> Vec<&'t str> {
+        let mut seen = HashSet::new();
+
+        self.tokens
+            .iter()
+            .filter_map(|token| match token {
+                Token::Placeholder(name, ..) if seen.insert(*name) => Some(*name),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Compiles the template into a [`CompiledTemplate`], which interns the
+    /// unique placeholder names into slots in order to render the same
+    /// template with many contexts without looking up duplicate names.
+    pub fn compile(&self) -> CompiledTemplate<'t> {
+        CompiledTemplate::new(self.source, &self.tokens)
     }
 
     /// Fill the template's placeholders using the provided `replacements` HashMap
@@ -112,12 +347,60 @@ impl<'t> Template<'t> {
     /// Fill the template's placeholders using the provided `replacements HashMap`
     /// in order to to infer values for the named placeholders.
     ///
-    /// Placeholders without an associated value will result in a `Error::PlaceholderError`.
+    /// Placeholders without an associated value will result in a `Error::MissingValue`.
     ///
     /// For a version that does not generate an error in case a placeholder is missing see
     /// [`Template::fill_with_hashmap`].
     pub fn fill_with_hashmap_strict(&self, replacements: &HashMap<&str, &str>) -> Result<String> {
         self.fill_with_function(|s| replacements.get(s).map(|s| Cow::from(*s)))
+            .map_err(|err| err.with_suggestions(replacements.keys().copied()))
+    }
+
+    /// Fill the template's placeholders using the provided `replacements` HashMap
+    /// in order to derive values for the named placeholders.
+    ///
+    /// Placeholders without an associated value are kept with the boundaries
+    /// that produced them, so the result can be used as a template again.
+    /// Comments, raw blocks and set-delimiter tags are not reproduced.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// # #[cfg(feature = "std")]
+    /// # use std::collections::HashMap;
+    /// # #[cfg(not(feature = "std"))]
+    /// # use hashbrown::HashMap;
+    /// let template = Template::new_with_placeholders("${first} {{ second }}", &[("${", "}"), ("{{", "}}")]);
+    ///
+    /// let mut table = HashMap::new();
+    /// table.insert("first", "text");
+    ///
+    /// assert_eq!(template.fill_with_hashmap_partial(&table), "text {{second}}");
+    /// ```
+    pub fn fill_with_hashmap_partial(&self, replacements: &HashMap<&str, &str>) -> String {
+        let mut result = String::new();
+
+        for segment in &self.tokens {
+            match segment {
+                Token::Text(s) => result.push_str(s),
+                Token::Placeholder(s, delimiters, spec) => match (replacements.get(s), spec) {
+                    (Some(value), None) => result.push_str(value),
+                    (Some(value), Some(spec)) => spec.write(&mut result, value),
+                    (None, _) => {
+                        let (start, end) = self.delimiters[*delimiters];
+                        result.push_str(start);
+                        result.push_str(s);
+                        if let Some(spec) = spec {
+                            result.push(':');
+                            result.push_str(&spec.to_string());
+                        }
+                        result.push_str(end);
+                    }
+                },
+            }
+        }
+
+        result
     }
 
     /// Fill the template's placeholders using the provided `replacements`
@@ -132,7 +415,7 @@ impl<'t> Template<'t> {
     /// derived in some other way.
     ///
     /// Placeholders without an associated value (the function returns `None`)
-    /// will result in a `Error::PlaceholderError`.
+    /// will result in a `Error::MissingValue`.
     ///
     /// This is the most general form of replacement; the other `fill_with_`
     /// methods are implemented in terms of this method.
@@ -155,35 +438,134 @@ impl<'t> Template<'t> {
     /// );
     /// assert_eq!(idx, 2);
     /// ```
-    pub fn fill_with_function<'a, F>(&self, mut replacements: F) -> Result<String>
+    pub fn fill_with_function<'a, F>(&self, replacements: F) -> Result<String>
     where
         F: FnMut(&'t str) -> Option<Cow<'a, str>> + 'a,
     {
         let mut result = String::new();
+        self.fill_into(&mut result, replacements)?;
+
+        Ok(result)
+    }
+
+    /// Appends the filled template to `result`, see [`Template::fill_with_function`].
+    fn fill_into<'a, F>(&self, result: &mut String, mut replacements: F) -> Result<()>
+    where
+        F: FnMut(&'t str) -> Option<Cow<'a, str>> + 'a,
+    {
+        self.try_fill_into(result, |s, _| Ok(replacements(s).map(Replacement::Text)))
+    }
 
+    /// Appends the filled template to `result`, stopping at the first error
+    /// returned by `replacements`, which also receives the format spec of
+    /// the placeholder.
+    fn try_fill_into<'a, F>(&self, result: &mut String, mut replacements: F) -> Result<()>
+    where
+        F: FnMut(&'t str, Option<&FormatSpec>) -> Result<Option<Replacement<'a>>>,
+    {
         for segment in &self.tokens {
             match segment {
                 Token::Text(s) => result.push_str(s),
-                Token::Placeholder(s) => match replacements(s) {
-                    Some(value) => result.push_str(&value),
-                    None => {
-                        let message = format!("missing value for placeholder named '{s}'.");
-                        return Err(Error::PlaceholderError(message));
-                    }
+                Token::Placeholder(s, _, spec) => match replacements(s, spec.as_ref())? {
+                    Some(value) => value.write(result, spec.as_ref()),
+                    None => return Err(Error::missing_value(self.source, s)),
                 },
             }
         }
 
+        Ok(())
+    }
+
+    /// Fill the template's placeholders using a fallible `replacements`
+    /// function, see [`Template::fill_with_function`].
+    ///
+    /// Placeholders without an associated value (the function returns
+    /// `Ok(None)`) will result in a `Error::MissingValue`, while the first
+    /// error returned by the function is wrapped in a `Error::ResolverError`
+    /// along with the placeholder name, and stops the replacement.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::{Error, Template};
+    /// # use std::borrow::Cow;
+    /// # use std::io;
+    /// let template = Template::new("{{user}}: {{secret}}");
+    ///
+    /// let result = template.try_fill_with_function(|key| match key {
+    ///     "user" => Ok(Some(Cow::Borrowed("ana"))),
+    ///     _ => Err(io::Error::new(io::ErrorKind::PermissionDenied, "access denied")),
+    /// });
+    ///
+    /// match result {
+    ///     Err(Error::ResolverError { name, source }) => {
+    ///         assert_eq!(name, "secret");
+    ///         assert_eq!(source.to_string(), "access denied");
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn try_fill_with_function<'a, F, E>(&self, mut replacements: F) -> Result<String>
+    where
+        F: FnMut(&'t str) -> core::result::Result<Option<Cow<'a, str>>, E> + 'a,
+        E: Into<Box<dyn core::error::Error + Send + Sync>>,
+    {
+        let mut result = String::new();
+
+        self.try_fill_into(&mut result, |s, _| {
+            replacements(s)
+                .map(|value| value.map(Replacement::Text))
+                .map_err(|err| Error::ResolverError {
+                    name: s.to_string(),
+                    source: err.into(),
+                })
+        })?;
+
         Ok(result)
     }
 
+    /// Fill the template's placeholders using the provided `replacements` context
+    /// in order to derive values for the named placeholders. The provided context
+    /// must implement [`PlaceholderContext`], usually through
+    /// `#[derive(PlaceholderContext)]`.
+    ///
+    /// Placeholders without an associated value will be replaced with an empty string.
+    ///
+    /// For a version that generates an error in case a placeholder is missing see
+    /// [`Template::fill_with_context_strict`].
+    pub fn fill_with_context<C>(&self, replacements: &C) -> String
+    where
+        C: PlaceholderContext + ?Sized,
+    {
+        self.fill_with_function(|s| Some(replacements.get(s).unwrap_or(Cow::Borrowed(""))))
+            .unwrap()
+    }
+
+    /// Fill the template's placeholders using the provided `replacements` context
+    /// in order to infer values for the named placeholders. The provided context
+    /// must implement [`PlaceholderContext`], usually through
+    /// `#[derive(PlaceholderContext)]`.
+    ///
+    /// Placeholders without an associated value will result in a `Error::MissingValue`.
+    ///
+    /// For a version that does not generate an error in case a placeholder is missing see
+    /// [`Template::fill_with_context`].
+    pub fn fill_with_context_strict<C>(&self, replacements: &C) -> Result<String>
+    where
+        C: PlaceholderContext + ?Sized,
+    {
+        self.fill_with_function(|s| replacements.get(s))
+            .map_err(|err| err.with_suggestions(replacements.keys()))
+    }
+
     #[cfg(feature = "struct_context")]
     /// Fill the template's placeholders using the provided `replacements struct`
     /// in order to to derive values for the named placeholders. The provided struct
-    /// must implement `serde::Serialize`.
+    /// must implement `serde::Serialize`. Dotted names such as `{{user.first}}`
+    /// are looked up in nested structs or maps.
     ///
     /// Placeholders without an associated value or with values that cannot be converted
-    /// to an str will be replaced with an empty string.
+    /// to an str will be replaced with an empty string. Numbers are converted
+    /// when the placeholder has a format spec, such as `{{price:.2}}`.
     ///
     /// For a version that generates an error in case a placeholder is missing see
     /// [`Template::fill_with_struct_strict`].
@@ -193,13 +575,17 @@ impl<'t> Template<'t> {
     {
         let replacements = serde_json::to_value(replacements)?;
 
-        let result = self
-            .fill_with_function(|s| {
-                Some(Cow::Borrowed(
-                    replacements.get(s).and_then(|v| v.as_str()).unwrap_or(""),
-                ))
-            })
-            .unwrap();
+        let mut result = String::new();
+
+        self.try_fill_into(&mut result, |s, spec| {
+            Ok(Some(match context::lookup(&replacements, s) {
+                Some(Value::String(value)) => Replacement::Text(Cow::Borrowed(value.as_str())),
+                Some(Value::Number(value)) if spec.is_some() => {
+                    Replacement::Number(value.to_string())
+                }
+                _ => Replacement::Text(Cow::Borrowed("")),
+            }))
+        })?;
 
         Ok(result)
     }
@@ -207,10 +593,13 @@ impl<'t> Template<'t> {
     #[cfg(feature = "struct_context")]
     /// Fill the template's placeholders using the provided `replacements struct`
     /// in order to to infer values for the named placeholders. The provided struct
-    /// must implement `serde::Serialize`.
+    /// must implement `serde::Serialize`. Dotted names such as `{{user.first}}`
+    /// are looked up in nested structs or maps.
     ///
-    /// Placeholders without an associated value or with values that cannot be converted
-    /// to an str will result in a `Error::PlaceholderError`.
+    /// Placeholders without an associated value (or with a `null` one) will result
+    /// in a `Error::MissingValue`, and values that are not strings will result in a
+    /// `Error::NonScalarValue`. Numbers are converted when the placeholder has a
+    /// format spec, such as `{{price:.2}}`.
     ///
     /// For a version that does not generate an error in case a placeholder is missing see
     /// [`Template::fill_with_struct`].
@@ -220,11 +609,32 @@ impl<'t> Template<'t> {
     {
         let replacements = serde_json::to_value(replacements)?;
 
-        self.fill_with_function(|s| {
-            replacements
-                .get(s)
-                .and_then(|v| v.as_str().map(Cow::Borrowed))
+        let mut result = String::new();
+
+        self.try_fill_into(&mut result, |s, spec| {
+            match context::lookup(&replacements, s) {
+                None | Some(Value::Null) => Ok(None),
+                Some(Value::String(value)) => Ok(Some(Replacement::Text(Cow::Borrowed(value)))),
+                Some(Value::Number(value)) if spec.is_some() => {
+                    Ok(Some(Replacement::Number(value.to_string())))
+                }
+                Some(value) => Err(Error::NonScalarValue {
+                    name: s.to_string(),
+                    kind: match value {
+                        Value::Bool(_) => "boolean",
+                        Value::Number(_) => "number",
+                        Value::Array(_) => "array",
+                        _ => "object",
+                    },
+                }),
+            }
         })
+        .map_err(|err| match replacements.as_object() {
+            Some(object) => err.with_suggestions(object.keys().map(String::as_str)),
+            None => err,
+        })?;
+
+        Ok(result)
     }
 }
 
@@ -239,7 +649,10 @@ mod tests {
     #[cfg(feature = "std")]
     use std::collections::HashMap;
 
-    use super::Template;
+    use super::{Error, NameRules, PlaceholderContext, Template};
+
+    #[cfg(feature = "locale")]
+    use super::Locale;
 
     #[cfg(not(feature = "std"))]
     use hashbrown::HashMap;
@@ -247,6 +660,237 @@ mod tests {
     #[cfg(feature = "struct_context")]
     use serde::Serialize;
 
+    // -------------------
+    // | strict parsing |
+    // -------------------
+
+    #[test]
+    fn test_strict_parsing_valid_template() {
+        let mut table = HashMap::new();
+        table.insert("placeholder", "world");
+
+        assert_eq!(
+            Template::new_strict("hello {{ placeholder }}")
+                .unwrap()
+                .fill_with_hashmap(&table),
+            "hello world"
+        );
+    }
+
+    #[test]
+    fn test_strict_parsing_unclosed_placeholder() {
+        assert_eq!(
+            Template::new_strict("hello {{placeholder")
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Err("Error while parsing template. Reason: unclosed placeholder at 6..19.".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_strict_parsing_invalid_name() {
+        let rules = NameRules {
+            identifier: true,
+            ..NameRules::default()
+        };
+
+        assert_eq!(
+            Template::new_with_rules("hello [first name]", "[", "]", rules)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Err("Error while parsing template. Reason: invalid placeholder name 'first name' at 7..17, the name is not an identifier.".to_owned())
+        );
+    }
+
+    // ----------------
+    // | placeholders |
+    // ----------------
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(
+            Template::new("{{first}} {{! comment }} {{second}} {{first}} {{third").placeholders(),
+            vec!["first", "second"]
+        );
+    }
+
+    #[test]
+    fn test_comments_are_not_rendered() {
+        let mut table = HashMap::new();
+        table.insert("placeholder", "world");
+
+        assert_eq!(
+            Template::new("hello {{! a note for translators }}{{placeholder}}")
+                .fill_with_hashmap_strict(&table)
+                .unwrap(),
+            "hello world"
+        );
+    }
+
+    // ----------------------
+    // | format specifiers |
+    // ----------------------
+
+    #[test]
+    fn test_format_specifiers() {
+        let mut table = HashMap::new();
+        table.insert("item", "tea");
+        table.insert("price", "3.5");
+        table.insert("count", "7");
+
+        assert_eq!(
+            Template::new("[{{item:<6}}|{{ price : >8.2 }}|{{count:+04}}]")
+                .fill_with_hashmap_strict(&table)
+                .unwrap(),
+            "[tea   |    3.50|+007]"
+        );
+        assert_eq!(
+            Template::new("{{item:*^7.2}}").fill_with_hashmap(&table),
+            "**te***"
+        );
+    }
+
+    #[test]
+    fn test_format_specifiers_invalid_spec_is_part_of_the_name() {
+        let mut table = HashMap::new();
+        table.insert("ns:key", "value");
+
+        let template = Template::new("{{ns:key}} {{ns:key:>7}}");
+
+        assert_eq!(template.placeholders(), ["ns:key"]);
+        assert_eq!(template.fill_with_hashmap(&table), "value   value");
+    }
+
+    #[test]
+    fn test_format_specifiers_trailing_colon_keeps_name() {
+        let mut table = HashMap::new();
+        table.insert("a:5", "five");
+        table.insert("ns:", "namespace");
+
+        let template = Template::new("{{a:5:}} {{ns::}}");
+
+        assert_eq!(template.placeholders(), ["a:5", "ns:"]);
+        assert_eq!(template.fill_with_hashmap(&table), "five namespace");
+        assert_eq!(
+            template.fill_with_hashmap_partial(&HashMap::new()),
+            "{{a:5:}} {{ns::}}"
+        );
+    }
+
+    #[test]
+    fn test_format_specifiers_locale_styles_split_names() {
+        // The same names with or without the `locale` feature.
+        let template = Template::new("{{total:currency}} {{day:date-long}} {{ns:key}}");
+
+        assert_eq!(template.placeholders(), ["total", "day", "ns:key"]);
+    }
+
+    #[test]
+    fn test_format_specifiers_text_looking_like_numbers() {
+        let mut table = HashMap::new();
+        table.insert("id", "123");
+
+        assert_eq!(
+            Template::new("[{{id:6}}|{{id:>6}}|{{id:6.1}}]").fill_with_hashmap(&table),
+            "[123   |   123| 123.0]"
+        );
+    }
+
+    #[test]
+    fn test_format_specifiers_strict_parsing() {
+        let rules = NameRules {
+            identifier: true,
+            ..NameRules::default()
+        };
+
+        assert!(Template::new_with_rules("{{price:>10.2}}", "{{", "}}", rules).is_ok());
+        assert!(matches!(
+            Template::new_with_rules("{{price:>10.x}}", "{{", "}}", rules),
+            Err(Error::InvalidName { .. })
+        ));
+    }
+
+    #[test]
+    fn test_format_specifiers_partial_fill() {
+        let table = HashMap::new();
+
+        assert_eq!(
+            Template::new("{{ price : *>10.2 }} {{name:}}").fill_with_hashmap_partial(&table),
+            "{{price:*>10.2}} {{name:}}"
+        );
+    }
+
+    #[cfg(feature = "locale")]
+    #[test]
+    fn test_locale_styles() {
+        let mut table = HashMap::new();
+        table.insert("total", "1234.5");
+        table.insert("ratio", "0.256");
+        table.insert("day", "2024-03-05T10:00:00Z");
+        table.insert("note", "n/a");
+
+        let template = Template::new(
+            "{{total:currency}}|{{total:decimal.2}}|{{ratio:percent.1}}|{{day:date-short}}|{{note:currency}}",
+        );
+
+        assert_eq!(
+            template.fill_with_hashmap(&table),
+            "$1,234.50|1,234.50|25.6%|3/5/24|n/a"
+        );
+        assert_eq!(
+            template
+                .with_locale(Locale::get("de-DE").unwrap())
+                .fill_with_hashmap(&table),
+            "1.234,50\u{a0}€|1.234,50|25,6\u{a0}%|05.03.24|n/a"
+        );
+    }
+
+    #[cfg(feature = "locale")]
+    #[test]
+    fn test_locale_styles_padding() {
+        let mut table = HashMap::new();
+        table.insert("total", "5");
+        table.insert("day", "2024-03-05");
+
+        let template = Template::new("[{{total:10currency}}|{{day:*>12date-medium}}]")
+            .with_locale(Locale::get("fr-FR").unwrap());
+
+        assert_eq!(
+            template.fill_with_hashmap(&table),
+            "[    5,00\u{a0}€|*5 mars 2024]"
+        );
+        assert_eq!(
+            template.compile().render(&["5", "2024-03-05"]),
+            "[    5,00\u{a0}€|*5 mars 2024]"
+        );
+        assert_eq!(
+            template.fill_with_hashmap_partial(&HashMap::new()),
+            "[{{total:10currency}}|{{day:*>12date-medium}}]"
+        );
+    }
+
+    #[cfg(all(feature = "locale", feature = "struct_context"))]
+    #[test]
+    fn test_locale_styles_struct_numbers() {
+        #[derive(Serialize)]
+        struct Context {
+            total: f64,
+            count: u64,
+        }
+        let context = Context {
+            total: 1234.56,
+            count: 12345678901234567890,
+        };
+
+        assert_eq!(
+            Template::new("{{total:currency}} {{count:decimal}}")
+                .with_locale(Locale::get("es-ES").unwrap())
+                .fill_with_struct_strict(&context)
+                .unwrap(),
+            "1234,56\u{a0}€ 12.345.678.901.234.567.890"
+        );
+    }
+
     // ---------------------
     // | fill_with_hashmap |
     // ---------------------
@@ -338,6 +982,36 @@ mod tests {
         );
     }
 
+    // -----------------------------
+    // | fill_with_hashmap_partial |
+    // -----------------------------
+
+    #[test]
+    fn test_hashmap_partial_keeps_original_boundaries() {
+        let mut table = HashMap::new();
+        table.insert("first", "one");
+
+        let template = Template::new_with_placeholders(
+            "${first} ${second} {{ first }} {{third}}",
+            &[("${", "}"), ("{{", "}}")],
+        );
+
+        assert_eq!(
+            template.fill_with_hashmap_partial(&table),
+            "one ${second} one {{third}}"
+        );
+    }
+
+    #[test]
+    fn test_hashmap_partial_after_set_delimiters() {
+        let table = HashMap::new();
+
+        assert_eq!(
+            Template::new("{{first}} {{=<% %>=}}<%second%>").fill_with_hashmap_partial(&table),
+            "{{first}} <%second%>"
+        );
+    }
+
     // ----------------------------
     // | fill_with_hashmap_strict |
     // ----------------------------
@@ -444,6 +1118,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hashmap_strict_missing_replacements_span() {
+        let table = HashMap::new();
+
+        match Template::new("hello {{ first }} {{second}}").fill_with_hashmap_strict(&table) {
+            Err(Error::MissingValue { name, span, .. }) => {
+                assert_eq!(name, "first");
+                assert_eq!(span, 9..14);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hashmap_strict_missing_replacements_suggestions() {
+        let mut table = HashMap::new();
+        table.insert("username", "ana");
+        table.insert("usernames", "ana, bo");
+        table.insert("email", "ana@example.com");
+
+        assert_eq!(
+            Template::new("hello {{usrname}}").fill_with_hashmap_strict(&table).map_err(|e| e.to_string()),
+            Err("Error while replacing placeholder. Reason: missing value for placeholder named 'usrname', did you mean 'username' or 'usernames'?".to_owned())
+        );
+
+        match Template::new("{{emial}} {{address}}").fill_with_hashmap_strict(&table) {
+            Err(Error::MissingValue { suggestions, .. }) => assert_eq!(suggestions, ["email"]),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
     // ----------------------
     // | fill_with_function |
     // ----------------------
@@ -467,6 +1172,232 @@ mod tests {
         assert_eq!(kw, vec!["foo", "bar"]);
     }
 
+    // --------------------------
+    // | try_fill_with_function |
+    // --------------------------
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_try_function_replacements() {
+        let template = Template::new("hello {{foo}} {{bar}}");
+
+        let result =
+            template.try_fill_with_function(|s| Ok::<_, std::io::Error>(Some(Cow::Borrowed(s))));
+
+        assert_eq!(result.unwrap(), "hello foo bar");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_try_function_replacements_missing_value() {
+        let template = Template::new("hello {{foo}}");
+
+        assert_eq!(
+            template
+                .try_fill_with_function(|_| Ok::<_, std::io::Error>(None))
+                .map_err(|e| e.to_string()),
+            Err("Error while replacing placeholder. Reason: missing value for placeholder named 'foo'.".to_owned())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_try_function_replacements_error() {
+        use std::error::Error as _;
+
+        let template = Template::new("hello {{foo}} {{bar}} {{baz}}");
+        let mut calls = Vec::new();
+
+        let err = template
+            .try_fill_with_function(|s| {
+                calls.push(s);
+                match s {
+                    "bar" => Err(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        "no such file",
+                    )),
+                    _ => Ok(Some(Cow::Borrowed(s))),
+                }
+            })
+            .unwrap_err();
+
+        assert_eq!(calls, vec!["foo", "bar"]);
+        assert_eq!(
+            err.to_string(),
+            "Error while replacing placeholder named 'bar'. Reason: no such file"
+        );
+
+        let source = err.source().unwrap();
+        assert_eq!(
+            source.downcast_ref::<std::io::Error>().unwrap().kind(),
+            std::io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_try_function_replacements_error_source() {
+        use core::error::Error as _;
+
+        #[derive(Debug)]
+        struct Denied;
+
+        impl core::fmt::Display for Denied {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("denied")
+            }
+        }
+
+        impl core::error::Error for Denied {}
+
+        let err = Template::new("{{secret}}")
+            .try_fill_with_function(|_| Err::<Option<Cow<str>>, _>(Denied))
+            .unwrap_err();
+
+        assert!(matches!(&err, Error::ResolverError { name, .. } if name == "secret"));
+        assert!(err.source().unwrap().is::<Denied>());
+    }
+
+    // ---------------------
+    // | fill_with_context |
+    // ---------------------
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_context_derive_replacements() {
+        #[derive(PlaceholderContext)]
+        struct Context {
+            first: Cow<'static, str>,
+            second: &'static str,
+            count: u32,
+        }
+        let context = Context {
+            first: Cow::Borrowed("one"),
+            second: "two",
+            count: 3,
+        };
+
+        assert_eq!(
+            Template::new("{{first}} {{second}} {{count}}").fill_with_context(&context),
+            "one two 3"
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_context_derive_numbers_are_text() {
+        #[derive(PlaceholderContext)]
+        struct Context {
+            count: u32,
+        }
+        let context = Context { count: 3 };
+
+        // Derived fields are converted to text, so only numeric specs format
+        // them as numbers.
+        assert_eq!(
+            Template::new("[{{count:4}}|{{count:>4}}|{{count:04}}|{{count:.1}}]")
+                .fill_with_context(&context),
+            "[3   |   3|0003|3.0]"
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_context_derive_rename_and_skip() {
+        #[derive(PlaceholderContext)]
+        struct Context {
+            #[placeholder(rename = "first-name")]
+            first: &'static str,
+            #[placeholder(skip)]
+            #[allow(dead_code)]
+            secret: &'static str,
+        }
+        let context = Context {
+            first: "one",
+            secret: "hidden",
+        };
+
+        assert_eq!(
+            Template::new("{{first-name}} {{first}} {{secret}}").fill_with_context(&context),
+            "one  "
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_context_derive_strict_missing_replacements() {
+        #[derive(PlaceholderContext)]
+        struct Context {
+            placeholder: Option<&'static str>,
+        }
+        let context = Context { placeholder: None };
+
+        assert_eq!(
+            Template::new("hello {{placeholder}}").fill_with_context_strict(&context).map_err(|e| e.to_string()),
+            Err("Error while replacing placeholder. Reason: missing value for placeholder named 'placeholder'.".to_owned())
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_context_derive_strict_suggestions() {
+        #[derive(PlaceholderContext)]
+        struct Context {
+            #[placeholder(rename = "first_name")]
+            first: &'static str,
+            email: &'static str,
+            #[placeholder(skip)]
+            #[allow(dead_code)]
+            first_names: &'static str,
+        }
+        let context = Context {
+            first: "Ana",
+            email: "ana@example.com",
+            first_names: "hidden",
+        };
+
+        assert_eq!(
+            Template::new("{{frist_name}}").fill_with_context_strict(&context).map_err(|e| e.to_string()),
+            Err("Error while replacing placeholder. Reason: missing value for placeholder named 'frist_name', did you mean 'first_name'?".to_owned())
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_context_derive_keys_skip_absent_values() {
+        #[derive(PlaceholderContext)]
+        struct Context {
+            name: &'static str,
+            nickname: Option<&'static str>,
+            age: Option<u8>,
+        }
+        let context = Context {
+            name: "Ana",
+            nickname: None,
+            age: Some(30),
+        };
+
+        assert_eq!(context.keys(), ["name", "age"]);
+        assert_eq!(Template::new("{{name}}").unused_keys(&context), ["age"]);
+    }
+
+    #[test]
+    fn test_context_manual_implementation() {
+        struct Context;
+
+        impl PlaceholderContext for Context {
+            fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+                Some(Cow::Owned(key.to_uppercase()))
+            }
+        }
+
+        assert_eq!(
+            Template::new("hello {{placeholder}}")
+                .fill_with_context_strict(&Context)
+                .unwrap(),
+            "hello PLACEHOLDER"
+        );
+    }
+
     // --------------------
     // | fill_with_struct |
     // --------------------
@@ -776,4 +1707,92 @@ mod tests {
             Err("Error while replacing placeholder. Reason: missing value for placeholder named 'placeholder'.".to_owned())
         );
     }
+
+    #[cfg(feature = "struct_context")]
+    #[test]
+    fn test_struct_strict_missing_replacements_suggestions() {
+        #[derive(Serialize)]
+        struct Context {
+            username: String,
+        }
+        let context = Context {
+            username: "ana".to_string(),
+        };
+
+        assert_eq!(
+            Template::new("hello {{usrname}}").fill_with_struct_strict(&context).map_err(|e| e.to_string()),
+            Err("Error while replacing placeholder. Reason: missing value for placeholder named 'usrname', did you mean 'username'?".to_owned())
+        );
+    }
+
+    #[cfg(feature = "struct_context")]
+    #[test]
+    fn test_struct_strict_non_scalar_replacements() {
+        #[derive(Serialize)]
+        struct Context {
+            list: Vec<u8>,
+            count: u8,
+            nothing: Option<u8>,
+        }
+        let context = Context {
+            list: vec![1, 2],
+            count: 3,
+            nothing: None,
+        };
+
+        assert_eq!(
+            Template::new("{{list}}").fill_with_struct_strict(&context).map_err(|e| e.to_string()),
+            Err("Error while replacing placeholder. Reason: value of type array for placeholder named 'list' cannot be converted to a string.".to_owned())
+        );
+        assert!(matches!(
+            Template::new("{{count}}").fill_with_struct_strict(&context),
+            Err(Error::NonScalarValue { kind: "number", .. })
+        ));
+        assert!(matches!(
+            Template::new("{{nothing}}").fill_with_struct_strict(&context),
+            Err(Error::MissingValue { .. })
+        ));
+    }
+
+    #[cfg(feature = "struct_context")]
+    #[test]
+    fn test_struct_format_specifiers_convert_numbers() {
+        #[derive(Serialize)]
+        struct Context {
+            price: f64,
+            count: u8,
+            flag: bool,
+        }
+        let context = Context {
+            price: 1234.5,
+            count: 3,
+            flag: true,
+        };
+
+        assert_eq!(
+            Template::new("{{price:>10.2}} {{count:03}}")
+                .fill_with_struct_strict(&context)
+                .unwrap(),
+            "   1234.50 003"
+        );
+        assert_eq!(
+            Template::new("{{price:.1}} {{count}}")
+                .fill_with_struct(&context)
+                .unwrap(),
+            "1234.5 "
+        );
+        assert_eq!(
+            Template::new("[{{count:4}}]")
+                .fill_with_struct(&context)
+                .unwrap(),
+            format!("[{:4}]", context.count)
+        );
+        assert!(matches!(
+            Template::new("{{flag:>5}}").fill_with_struct_strict(&context),
+            Err(Error::NonScalarValue {
+                kind: "boolean",
+                ..
+            })
+        ));
+    }
 }
@@ -0,0 +1,373 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::owned::OwnedTemplate;
+use crate::{Template, DEFAULT_END_PLACEHOLDER, DEFAULT_START_PLACEHOLDER};
+
+/// Templates loaded from the files of a directory, available with the `std`
+/// feature.
+///
+/// Every file under the directory and its subdirectories is parsed strictly
+/// into an [`OwnedTemplate`], named after its path relative to the
+/// directory without the extension and with `/` as separator, so
+/// `templates/emails/welcome.txt` is named `emails/welcome`. Hidden files
+/// and directories, starting with a `.`, are skipped.
+///
+/// Example:
+/// ```rust,no_run
+/// # use text_placeholder::TemplateDir;
+/// # use std::collections::HashMap;
+/// let templates = TemplateDir::load("templates").unwrap();
+///
+/// let welcome = templates.get("emails/welcome").unwrap();
+/// let text = welcome.fill_with_hashmap(&HashMap::from([("name", "Ana")]));
+/// ```
+#[derive(Default)]
+pub struct TemplateDir {
+    templates: HashMap<String, OwnedTemplate>,
+}
+
+impl TemplateDir {
+    /// Loads the templates under `dir` with boundaries specified by the
+    /// handlebars syntax.
+    ///
+    /// Files and subdirectories that cannot be read or parsed, such as
+    /// dangling symbolic links, do not stop the loading, they are all
+    /// reported at once in an `Error::Load`. Failing to read `dir` itself
+    /// results in an `Error::Io`.
+    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        Self::load_with_placeholder(dir, DEFAULT_START_PLACEHOLDER, DEFAULT_END_PLACEHOLDER)
+    }
+
+    /// Loads the templates under `dir` with boundaries specified by the
+    /// `start` and `end` arguments, see [`TemplateDir::load`].
+    pub fn load_with_placeholder<P: AsRef<Path>>(dir: P, start: &str, end: &str) -> Result<Self> {
+        let dir = dir.as_ref();
+        let mut files = Vec::new();
+        let mut errors = Vec::new();
+        walk(dir, &mut files, &mut errors)?;
+        files.sort();
+
+        let mut templates = HashMap::new();
+
+        for path in files {
+            let relative = path.strip_prefix(dir).unwrap_or(&path).to_path_buf();
+            let name = template_name(&relative);
+
+            match read_template(&path, start, end) {
+                Ok(_) if templates.contains_key(&name) => {
+                    errors.push((relative, Error::DuplicateTemplate { name }));
+                }
+                Ok(template) => {
+                    templates.insert(name, template);
+                }
+                Err(err) => errors.push((relative, err)),
+            }
+        }
+
+        errors.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        if errors.is_empty() {
+            Ok(Self { templates })
+        } else {
+            Err(Error::Load { errors })
+        }
+    }
+
+    /// Returns the template named `name`, such as `emails/welcome`.
+    pub fn get(&self, name: &str) -> Option<Template<'_>> {
+        self.templates.get(name).map(OwnedTemplate::template)
+    }
+
+    /// Returns the owned template named `name`.
+    pub fn get_owned(&self, name: &str) -> Option<&OwnedTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Returns the names of the loaded templates, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.templates.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Returns the number of loaded templates.
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Returns `true` when no template was loaded.
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Returns the loaded templates keyed by name.
+    pub fn into_inner(self) -> HashMap<String, OwnedTemplate> {
+        self.templates
+    }
+}
+
+/// Reads and parses strictly the template at `path`.
+pub(crate) fn read_template(path: &Path, start: &str, end: &str) -> Result<OwnedTemplate> {
+    let text = fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    OwnedTemplate::new_with_placeholder_strict(text, start, end)
+}
+
+/// Collects the paths of the non-hidden files under `dir`.
+///
+/// Symbolic links are followed, but each directory is visited once so that
+/// a link to one of its ancestors does not recurse forever. Entries that
+/// cannot be read are added to `errors` along with their path relative to
+/// `dir`, failing to read `dir` itself results in an `Error::Io`.
+pub(crate) fn walk(
+    dir: &Path,
+    files: &mut Vec<PathBuf>,
+    errors: &mut Vec<(PathBuf, Error)>,
+) -> Result<()> {
+    let mut failures = Vec::new();
+    walk_dir(dir, files, &mut HashSet::new(), &mut failures);
+
+    for (path, source) in failures {
+        if path == dir {
+            return Err(Error::Io { path, source });
+        }
+
+        let relative = path.strip_prefix(dir).unwrap_or(&path).to_path_buf();
+        errors.push((relative, Error::Io { path, source }));
+    }
+
+    Ok(())
+}
+
+fn walk_dir(
+    dir: &Path,
+    files: &mut Vec<PathBuf>,
+    visited: &mut HashSet<PathBuf>,
+    failures: &mut Vec<(PathBuf, io::Error)>,
+) {
+    let entries = match fs::canonicalize(dir) {
+        Ok(canonical) => {
+            if !visited.insert(canonical) {
+                return;
+            }
+            fs::read_dir(dir)
+        }
+        Err(err) => Err(err),
+    };
+
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(err) => return failures.push((dir.to_path_buf(), err)),
+    };
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                failures.push((dir.to_path_buf(), err));
+                continue;
+            }
+        };
+
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+
+        let path = entry.path();
+        // Follows symbolic links.
+        match fs::metadata(&path) {
+            Ok(metadata) if metadata.is_dir() => walk_dir(&path, files, visited, failures),
+            Ok(metadata) if metadata.is_file() => files.push(path),
+            Ok(_) => {}
+            Err(err) => failures.push((path, err)),
+        }
+    }
+}
+
+/// The relative path of a template without its extension, with `/` as
+/// separator on every platform.
+pub(crate) fn template_name(relative: &Path) -> String {
+    let without_extension = relative.with_extension("");
+
+    without_extension
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use super::{template_name, TemplateDir};
+    use crate::Error;
+
+    /// A temporary directory removed when dropped.
+    pub(crate) struct TempDir(pub(crate) PathBuf);
+
+    impl TempDir {
+        pub(crate) fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "text_placeholder_{}_{}",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+
+        pub(crate) fn write(&self, relative: &str, text: &str) {
+            let path = self.0.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_template_name() {
+        assert_eq!(template_name(Path::new("welcome.txt")), "welcome");
+        assert_eq!(
+            template_name(Path::new("emails/welcome.html")),
+            "emails/welcome"
+        );
+        assert_eq!(template_name(Path::new("README")), "README");
+        assert_eq!(template_name(Path::new("a/b.tar.gz")), "a/b.tar");
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = TempDir::new("load");
+        dir.write("welcome.txt", "Welcome {{name}}!");
+        dir.write("emails/reset.html", "<p>Reset {{ link }}</p>");
+        dir.write(".hidden/ignored.txt", "{{unclosed");
+        dir.write(".ignored.txt", "{{unclosed");
+
+        let templates = TemplateDir::load(&dir.0).unwrap();
+
+        assert_eq!(templates.names(), ["emails/reset", "welcome"]);
+        assert_eq!(templates.len(), 2);
+        assert_eq!(
+            templates
+                .get("welcome")
+                .unwrap()
+                .fill_with_hashmap(&HashMap::from([("name", "Ana")])),
+            "Welcome Ana!"
+        );
+        assert_eq!(
+            templates.get("emails/reset").unwrap().placeholders(),
+            ["link"]
+        );
+        assert!(templates.get("missing").is_none());
+    }
+
+    #[test]
+    fn test_load_with_placeholder() {
+        let dir = TempDir::new("load_with_placeholder");
+        dir.write("a.txt", "[a] {{b}}");
+
+        let templates = TemplateDir::load_with_placeholder(&dir.0, "[", "]").unwrap();
+
+        assert_eq!(templates.get("a").unwrap().placeholders(), ["a"]);
+    }
+
+    #[test]
+    fn test_load_reports_every_error() {
+        let dir = TempDir::new("load_errors");
+        dir.write("valid.txt", "{{a}}");
+        dir.write("b/unclosed.txt", "text {{a");
+        dir.write("c.txt", "{{ }}");
+        dir.write("valid.html", "{{a}}");
+        fs::write(dir.0.join("binary.txt"), [0xff, 0xfe]).unwrap();
+
+        let errors = match TemplateDir::load(&dir.0) {
+            Err(Error::Load { errors }) => errors,
+            _ => panic!("expected a load error"),
+        };
+
+        let paths: Vec<_> = errors.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("b").join("unclosed.txt"),
+                PathBuf::from("binary.txt"),
+                PathBuf::from("c.txt"),
+                PathBuf::from("valid.txt"),
+            ]
+        );
+
+        assert!(matches!(errors[0].1, Error::Unclosed { .. }));
+        assert!(matches!(errors[1].1, Error::Io { .. }));
+        assert!(matches!(errors[2].1, Error::InvalidName { .. }));
+        assert!(matches!(&errors[3].1, Error::DuplicateTemplate { name } if name == "valid"));
+    }
+
+    #[test]
+    fn test_load_missing_dir() {
+        let dir = TempDir::new("load_missing");
+
+        assert!(matches!(
+            TemplateDir::load(dir.0.join("missing")),
+            Err(Error::Io { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_symlink_loop() {
+        let dir = TempDir::new("load_symlink_loop");
+        dir.write("welcome.txt", "Welcome {{name}}!");
+        dir.write("emails/reset.txt", "Reset {{link}}");
+        std::os::unix::fs::symlink("..", dir.0.join("emails").join("loop")).unwrap();
+
+        let templates = TemplateDir::load(&dir.0).unwrap();
+
+        assert_eq!(templates.names(), ["emails/reset", "welcome"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_dangling_symlink() {
+        let dir = TempDir::new("load_dangling_symlink");
+        dir.write("welcome.txt", "Welcome {{name}}!");
+        dir.write("emails/unclosed.txt", "{{unclosed");
+        std::os::unix::fs::symlink("missing.txt", dir.0.join("broken.txt")).unwrap();
+        std::os::unix::fs::symlink("missing", dir.0.join("emails").join("gone")).unwrap();
+
+        let errors = match TemplateDir::load(&dir.0) {
+            Err(Error::Load { errors }) => errors,
+            _ => panic!("expected a load error"),
+        };
+
+        let paths: Vec<_> = errors.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("broken.txt"),
+                PathBuf::from("emails").join("gone"),
+                PathBuf::from("emails").join("unclosed.txt"),
+            ]
+        );
+        assert!(
+            matches!(&errors[0].1, Error::Io { path, .. } if *path == dir.0.join("broken.txt"))
+        );
+        assert!(matches!(errors[1].1, Error::Io { .. }));
+        assert!(matches!(errors[2].1, Error::Unclosed { .. }));
+    }
+}
@@ -0,0 +1,741 @@
+use alloc::string::String;
+
+use crate::format::{is_integer, parse_number, Style};
+
+/// Length of the styles in which [`Locale::date`] formats a date.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DateStyle {
+    /// Numeric date, such as `3/5/24` in `en-US`.
+    Short,
+    /// Date with an abbreviated month, such as `Mar 5, 2024` in `en-US`.
+    Medium,
+    /// Date with the full month name, such as `March 5, 2024` in `en-US`.
+    Long,
+}
+
+/// Number and date formatting conventions of a locale, available with the
+/// `locale` feature.
+///
+/// Templates format values with the locale set by
+/// [`Template::with_locale`](crate::Template::with_locale) through the locale
+/// styles of their format specs, such as `{{total:currency}}`.
+///
+/// The data of every supported locale is bundled with the crate, see
+/// [`Locale::get`]. Values are taken as text, as provided by a context, so
+/// the formatting methods return `None` when a value is not a finite number
+/// or a date. Integers keep all their digits, even the ones that do not fit
+/// in a `f64`.
+///
+/// Example:
+/// ```rust
+/// # use text_placeholder::{DateStyle, Locale};
+/// let de = Locale::get("de-DE").unwrap();
+/// let us = Locale::get("en-US").unwrap();
+///
+/// assert_eq!(de.currency("1234.56").unwrap(), "1.234,56\u{a0}€");
+/// assert_eq!(us.currency("1234.56").unwrap(), "$1,234.56");
+/// assert_eq!(de.date("2024-03-05T10:00:00Z", DateStyle::Long).unwrap(), "5. März 2024");
+/// ```
+#[derive(PartialEq, Debug)]
+pub struct Locale {
+    tag: &'static str,
+    decimal: char,
+    group: &'static str,
+    /// Minimum number of digits before the first group separator, for
+    /// instance `es-ES` writes `1234` but `12.345`.
+    min_grouping: usize,
+    currency_prefix: &'static str,
+    currency_suffix: &'static str,
+    currency_digits: usize,
+    percent_suffix: &'static str,
+    date_short: &'static str,
+    date_medium: &'static str,
+    date_long: &'static str,
+    months: [&'static str; 12],
+    months_short: [&'static str; 12],
+}
+
+const EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const JA_MONTHS: [&str; 12] = [
+    "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+];
+
+/// Bundled locales, the first locale of a language is its default one.
+static LOCALES: [Locale; 6] = [
+    Locale {
+        tag: "en-US",
+        decimal: '.',
+        group: ",",
+        min_grouping: 1,
+        currency_prefix: "$",
+        currency_suffix: "",
+        currency_digits: 2,
+        percent_suffix: "%",
+        date_short: "M/d/yy",
+        date_medium: "MMM d, y",
+        date_long: "MMMM d, y",
+        months: EN_MONTHS,
+        months_short: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+    },
+    Locale {
+        tag: "en-GB",
+        decimal: '.',
+        group: ",",
+        min_grouping: 1,
+        currency_prefix: "£",
+        currency_suffix: "",
+        currency_digits: 2,
+        percent_suffix: "%",
+        date_short: "dd/MM/y",
+        date_medium: "d MMM y",
+        date_long: "d MMMM y",
+        months: EN_MONTHS,
+        months_short: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sept", "Oct", "Nov", "Dec",
+        ],
+    },
+    Locale {
+        tag: "de-DE",
+        decimal: ',',
+        group: ".",
+        min_grouping: 1,
+        currency_prefix: "",
+        currency_suffix: "\u{a0}€",
+        currency_digits: 2,
+        percent_suffix: "\u{a0}%",
+        date_short: "dd.MM.yy",
+        date_medium: "dd.MM.y",
+        date_long: "d. MMMM y",
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        months_short: [
+            "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+            "Dez.",
+        ],
+    },
+    Locale {
+        tag: "fr-FR",
+        decimal: ',',
+        group: "\u{202f}",
+        min_grouping: 1,
+        currency_prefix: "",
+        currency_suffix: "\u{a0}€",
+        currency_digits: 2,
+        percent_suffix: "\u{202f}%",
+        date_short: "dd/MM/y",
+        date_medium: "d MMM y",
+        date_long: "d MMMM y",
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        months_short: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+    },
+    Locale {
+        tag: "es-ES",
+        decimal: ',',
+        group: ".",
+        min_grouping: 2,
+        currency_prefix: "",
+        currency_suffix: "\u{a0}€",
+        currency_digits: 2,
+        percent_suffix: "\u{a0}%",
+        date_short: "d/M/yy",
+        date_medium: "d MMM y",
+        date_long: "d 'de' MMMM 'de' y",
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        months_short: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+    },
+    Locale {
+        tag: "ja-JP",
+        decimal: '.',
+        group: ",",
+        min_grouping: 1,
+        currency_prefix: "￥",
+        currency_suffix: "",
+        currency_digits: 0,
+        percent_suffix: "%",
+        date_short: "y/MM/dd",
+        date_medium: "y/MM/dd",
+        date_long: "y年M月d日",
+        months: JA_MONTHS,
+        months_short: JA_MONTHS,
+    },
+];
+
+impl Locale {
+    /// Returns the bundled locale for a language tag such as `de-DE` or
+    /// `de_DE`, ignoring case. A tag whose region is not bundled falls back
+    /// to the default locale of its language, for instance `de-AT` and `de`
+    /// return `de-DE`.
+    ///
+    /// The bundled locales are `en-US`, `en-GB`, `de-DE`, `fr-FR`, `es-ES`
+    /// and `ja-JP`.
+    pub fn get(tag: &str) -> Option<&'static Locale> {
+        let mut parts = tag.split(['-', '_']);
+        let language = parts.next()?;
+        let region = parts.next();
+
+        let same_language = |locale: &&Locale| locale.language().eq_ignore_ascii_case(language);
+
+        LOCALES
+            .iter()
+            .filter(same_language)
+            .find(|locale| {
+                region.is_some_and(|region| {
+                    locale.tag[locale.language().len() + 1..].eq_ignore_ascii_case(region)
+                })
+            })
+            .or_else(|| LOCALES.iter().find(same_language))
+    }
+
+    /// The locale of templates without one, `en-US`.
+    pub(crate) fn fallback() -> &'static Locale {
+        &LOCALES[0]
+    }
+
+    /// Returns the language tag of the locale, such as `de-DE`.
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    fn language(&self) -> &'static str {
+        self.tag.split('-').next().unwrap_or(self.tag)
+    }
+
+    /// Formats a number with the given number of decimals, such as
+    /// `1.234,50` for `1234.5` in `de-DE`.
+    pub fn decimal(&self, value: &str, decimals: usize) -> Option<String> {
+        self.format_number(value, decimals, false, "", "")
+    }
+
+    /// Formats an amount in the currency of the locale, with the usual
+    /// number of decimals of the currency, such as `$1,234.56` in `en-US`.
+    pub fn currency(&self, value: &str) -> Option<String> {
+        self.currency_with_decimals(value, self.currency_digits)
+    }
+
+    fn currency_with_decimals(&self, value: &str, decimals: usize) -> Option<String> {
+        self.format_number(
+            value,
+            decimals,
+            false,
+            self.currency_prefix,
+            self.currency_suffix,
+        )
+    }
+
+    /// Formats a ratio as a percentage with the given number of decimals,
+    /// such as `25,6 %` for `0.256` in `de-DE`.
+    pub fn percent(&self, value: &str, decimals: usize) -> Option<String> {
+        self.format_number(value, decimals, true, "", self.percent_suffix)
+    }
+
+    /// Formats the date of an RFC 3339 timestamp such as
+    /// `2024-03-05T10:00:00+01:00`, or of a date alone such as `2024-03-05`.
+    ///
+    /// The date is used as written, without converting it to another time
+    /// zone.
+    pub fn date(&self, value: &str, style: DateStyle) -> Option<String> {
+        let (year, month, day) = parse_rfc3339_date(value)?;
+        let pattern = match style {
+            DateStyle::Short => self.date_short,
+            DateStyle::Medium => self.date_medium,
+            DateStyle::Long => self.date_long,
+        };
+
+        let mut result = String::new();
+        let mut rest = pattern;
+
+        while let Some(c) = rest.chars().next() {
+            if c == '\'' {
+                // Quoted literal text, such as `'de'` in `es-ES`.
+                let end = rest[1..].find('\'').map_or(rest.len(), |end| end + 1);
+                result.push_str(&rest[1..end]);
+                rest = rest.get(end + 1..).unwrap_or("");
+                continue;
+            }
+
+            let len = rest.find(|other| other != c).unwrap_or(rest.len());
+            let month_name = |names: &[&'static str; 12]| names[month as usize - 1];
+
+            match (c, len) {
+                ('d', 1) => result.push_str(&format!("{}", day)),
+                ('d', _) => result.push_str(&format!("{:02}", day)),
+                ('M', 1) => result.push_str(&format!("{}", month)),
+                ('M', 2) => result.push_str(&format!("{:02}", month)),
+                ('M', 3) => result.push_str(month_name(&self.months_short)),
+                ('M', _) => result.push_str(month_name(&self.months)),
+                ('y', 2) => result.push_str(&format!("{:02}", year % 100)),
+                ('y', _) => result.push_str(&format!("{}", year)),
+                _ => result.push_str(&rest[..len]),
+            }
+
+            rest = &rest[len..];
+        }
+
+        Some(result)
+    }
+
+    /// Formats `value` rounded to `decimals` between `prefix` and `suffix`,
+    /// with the sign before the prefix and the separators of the locale.
+    fn format_number(
+        &self,
+        value: &str,
+        decimals: usize,
+        percent: bool,
+        prefix: &str,
+        suffix: &str,
+    ) -> Option<String> {
+        let (negative, digits) = round(value, decimals, percent)?;
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits.as_str(), None),
+        };
+
+        let mut result = String::new();
+        if negative {
+            result.push('-');
+        }
+        result.push_str(prefix);
+
+        if integer.len() > 3 && integer.len() >= 3 + self.min_grouping {
+            for (i, digit) in integer.char_indices() {
+                if i > 0 && (integer.len() - i) % 3 == 0 {
+                    result.push_str(self.group);
+                }
+                result.push(digit);
+            }
+        } else {
+            result.push_str(integer);
+        }
+
+        if let Some(fraction) = fraction {
+            result.push(self.decimal);
+            result.push_str(fraction);
+        }
+
+        result.push_str(suffix);
+        Some(result)
+    }
+}
+
+impl Style {
+    /// Formats `value` in `locale`, with `precision` decimals for numbers.
+    /// Without a precision, decimal numbers keep the decimals they are
+    /// written with, amounts use the decimals of the currency and
+    /// percentages have none.
+    pub(crate) fn format(
+        self,
+        locale: &Locale,
+        value: &str,
+        precision: Option<usize>,
+    ) -> Option<String> {
+        match self {
+            Style::Decimal => {
+                let decimals = precision.unwrap_or_else(|| written_decimals(value));
+                locale.decimal(value, decimals)
+            }
+            Style::Currency => match precision {
+                Some(decimals) => locale.currency_with_decimals(value, decimals),
+                None => locale.currency(value),
+            },
+            Style::Percent => locale.percent(value, precision.unwrap_or(0)),
+            Style::DateShort => locale.date(value, DateStyle::Short),
+            Style::DateMedium => locale.date(value, DateStyle::Medium),
+            Style::DateLong => locale.date(value, DateStyle::Long),
+        }
+    }
+}
+
+/// Number of digits after the decimal point of a number such as `3.25` or
+/// `3.25e2`.
+fn written_decimals(value: &str) -> usize {
+    let mantissa = value.split(['e', 'E']).next().unwrap_or(value);
+    mantissa
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len())
+}
+
+/// Rounds `value`, multiplied by 100 for a percentage, to `decimals` and
+/// splits it into its sign and its digits with `.` as decimal separator.
+///
+/// Integers are extended rather than converted in order to keep the digits
+/// that do not fit in a `f64`. Values rounded to zero, such as `-0.001`, are
+/// not negative.
+fn round(value: &str, decimals: usize, percent: bool) -> Option<(bool, String)> {
+    let mut number = parse_number(value)?;
+    if percent {
+        number *= 100.0;
+    }
+
+    if !number.is_finite() {
+        return None;
+    }
+
+    let text = if is_integer(value) {
+        let sign = if value.starts_with('-') { "-" } else { "" };
+        let digits = value.trim_start_matches(['-', '+']).trim_start_matches('0');
+        let scale = if percent && !digits.is_empty() {
+            "00"
+        } else {
+            ""
+        };
+        let digits = if digits.is_empty() { "0" } else { digits };
+
+        match decimals {
+            0 => format!("{}{}{}", sign, digits, scale),
+            _ => format!("{}{}{}.{:0<4$}", sign, digits, scale, "", decimals),
+        }
+    } else {
+        format!("{:.*}", decimals, number)
+    };
+
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.as_str()),
+    };
+    let negative = negative && digits.bytes().any(|b| matches!(b, b'1'..=b'9'));
+
+    Some((negative, digits.into()))
+}
+
+/// Parses the date of an RFC 3339 `date-time`, or a `full-date` alone.
+fn parse_rfc3339_date(value: &str) -> Option<(u32, u32, u32)> {
+    let date = value.get(..10)?;
+    let time = &value[10..];
+    let bytes = date.as_bytes();
+
+    if bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+
+    let year = parse_digits(&date[..4])?;
+    let month = parse_digits(&date[5..7])?;
+    let day = parse_digits(&date[8..])?;
+
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    if time.is_empty() || is_rfc3339_time(time) {
+        Some((year, month, day))
+    } else {
+        None
+    }
+}
+
+/// Checks the part of a `date-time` following the date, such as
+/// `T10:00:00.5+01:00`.
+fn is_rfc3339_time(time: &str) -> bool {
+    let time = match time.strip_prefix(|c| c == 'T' || c == 't' || c == ' ') {
+        Some(time) => time,
+        None => return false,
+    };
+
+    let (hour, minute, second) = match (time.get(..2), time.get(3..5), time.get(6..8)) {
+        (Some(hour), Some(minute), Some(second)) => (hour, minute, second),
+        _ => return false,
+    };
+    let bytes = time.as_bytes();
+
+    if bytes[2] != b':' || bytes[5] != b':' {
+        return false;
+    }
+
+    // Seconds go up to 60 because of leap seconds.
+    let valid = |s: &str, max: u32| parse_digits(s).is_some_and(|n| n <= max);
+    if !valid(hour, 23) || !valid(minute, 59) || !valid(second, 60) {
+        return false;
+    }
+
+    let mut offset = &time[8..];
+    if let Some(fraction) = offset.strip_prefix('.') {
+        let digits = fraction
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(fraction.len());
+        if digits == 0 {
+            return false;
+        }
+        offset = &fraction[digits..];
+    }
+
+    match offset {
+        "Z" | "z" => true,
+        _ => match offset.strip_prefix(|c| c == '+' || c == '-') {
+            Some(offset) if offset.len() == 5 && offset.as_bytes()[2] == b':' => {
+                valid(&offset[..2], 23) && valid(&offset[3..], 59)
+            }
+            _ => false,
+        },
+    }
+}
+
+fn parse_digits(s: &str) -> Option<u32> {
+    if s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DateStyle, Locale, Style};
+
+    fn locale(tag: &str) -> &'static Locale {
+        Locale::get(tag).unwrap()
+    }
+
+    #[test]
+    fn test_get() {
+        assert_eq!(locale("de-DE").tag(), "de-DE");
+        assert_eq!(locale("en_gb").tag(), "en-GB");
+        assert_eq!(locale("de-AT").tag(), "de-DE");
+        assert_eq!(locale("en").tag(), "en-US");
+        assert!(Locale::get("xx-XX").is_none());
+        assert!(Locale::get("").is_none());
+    }
+
+    #[test]
+    fn test_decimal() {
+        assert_eq!(
+            locale("en-US").decimal("1234567.891", 2).unwrap(),
+            "1,234,567.89"
+        );
+        assert_eq!(locale("de-DE").decimal("1234.5", 2).unwrap(), "1.234,50");
+        assert_eq!(
+            locale("fr-FR").decimal("-1234", 0).unwrap(),
+            "-1\u{202f}234"
+        );
+        assert_eq!(locale("en-US").decimal("123", 1).unwrap(), "123.0");
+        assert!(locale("en-US").decimal("abc", 2).is_none());
+    }
+
+    #[test]
+    fn test_decimal_min_grouping() {
+        assert_eq!(locale("es-ES").decimal("1234", 0).unwrap(), "1234");
+        assert_eq!(locale("es-ES").decimal("12345", 0).unwrap(), "12.345");
+    }
+
+    #[test]
+    fn test_currency() {
+        assert_eq!(
+            locale("de-DE").currency("1234.56").unwrap(),
+            "1.234,56\u{a0}€"
+        );
+        assert_eq!(locale("en-US").currency("1234.56").unwrap(), "$1,234.56");
+        assert_eq!(locale("en-US").currency("-5").unwrap(), "-$5.00");
+        assert_eq!(locale("en-GB").currency("0.5").unwrap(), "£0.50");
+        assert_eq!(locale("ja-JP").currency("1234.5").unwrap(), "￥1,234");
+        assert!(locale("en-US").currency("").is_none());
+    }
+
+    #[test]
+    fn test_non_finite_values() {
+        let us = locale("en-US");
+
+        assert!(us.currency("1e400").is_none());
+        assert!(us.decimal("-1e400", 2).is_none());
+        assert!(us.percent("1e307", 0).is_none());
+    }
+
+    #[test]
+    fn test_sign_after_rounding() {
+        let us = locale("en-US");
+
+        assert_eq!(us.currency("-0.001").unwrap(), "$0.00");
+        assert_eq!(us.decimal("-0.001", 2).unwrap(), "0.00");
+        assert_eq!(us.decimal("-0", 0).unwrap(), "0");
+        assert_eq!(us.percent("-0.00001", 1).unwrap(), "0.0%");
+        assert_eq!(us.currency("-0.005").unwrap(), "-$0.01");
+    }
+
+    #[test]
+    fn test_integers_keep_digits() {
+        let us = locale("en-US");
+
+        assert_eq!(
+            us.currency("12345678901234567890").unwrap(),
+            "$12,345,678,901,234,567,890.00"
+        );
+        assert_eq!(
+            us.percent("-12345678901234567890", 0).unwrap(),
+            "-1,234,567,890,123,456,789,000%"
+        );
+        assert_eq!(us.decimal("+007", 1).unwrap(), "7.0");
+        assert_eq!(us.percent("0", 1).unwrap(), "0.0%");
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(locale("en-US").percent("0.256", 0).unwrap(), "26%");
+        assert_eq!(locale("de-DE").percent("0.256", 1).unwrap(), "25,6\u{a0}%");
+        assert_eq!(
+            locale("fr-FR").percent("12.5", 0).unwrap(),
+            "1\u{202f}250\u{202f}%"
+        );
+    }
+
+    #[test]
+    fn test_date() {
+        let value = "2024-03-05T10:00:00Z";
+        let cases = [
+            ("en-US", ["3/5/24", "Mar 5, 2024", "March 5, 2024"]),
+            ("en-GB", ["05/03/2024", "5 Mar 2024", "5 March 2024"]),
+            ("de-DE", ["05.03.24", "05.03.2024", "5. März 2024"]),
+            ("fr-FR", ["05/03/2024", "5 mars 2024", "5 mars 2024"]),
+            ("es-ES", ["5/3/24", "5 mar 2024", "5 de marzo de 2024"]),
+            ("ja-JP", ["2024/03/05", "2024/03/05", "2024年3月5日"]),
+        ];
+
+        for (tag, expected) in cases {
+            let styles = [DateStyle::Short, DateStyle::Medium, DateStyle::Long];
+            for (style, expected) in styles.iter().zip(expected) {
+                assert_eq!(
+                    locale(tag).date(value, *style).unwrap(),
+                    expected,
+                    "{}",
+                    tag
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_date_rfc3339_variants() {
+        let us = locale("en-US");
+
+        for value in [
+            "2024-02-29",
+            "2024-02-29t23:59:60z",
+            "2024-02-29 08:15:00.123-05:00",
+            "2024-02-29T08:15:00+14:00",
+        ] {
+            assert_eq!(
+                us.date(value, DateStyle::Long).unwrap(),
+                "February 29, 2024",
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_date_invalid() {
+        let us = locale("en-US");
+
+        for value in [
+            "",
+            "2023-02-29",
+            "2024-13-01",
+            "2024-1-01",
+            "2024-01-01T10:00",
+            "2024-01-01T10:00:00",
+            "2024-01-01T24:00:00Z",
+            "2024-01-01T10:00:00.Z",
+            "2024-01-01T10:00:00+0100",
+            "2024-01-01x",
+            "March 5",
+        ] {
+            assert!(us.date(value, DateStyle::Short).is_none(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_style_format() {
+        let de = locale("de-DE");
+
+        assert_eq!(
+            Style::Currency.format(de, "1234.56", None).unwrap(),
+            "1.234,56\u{a0}€"
+        );
+        assert_eq!(
+            Style::Currency.format(de, "1234.56", Some(0)).unwrap(),
+            "1.235\u{a0}€"
+        );
+        assert_eq!(
+            Style::Decimal.format(de, "1234.5", None).unwrap(),
+            "1.234,5"
+        );
+        assert_eq!(Style::Decimal.format(de, "1234", None).unwrap(), "1.234");
+        assert_eq!(
+            Style::Decimal.format(de, "1234.5", Some(2)).unwrap(),
+            "1.234,50"
+        );
+        assert_eq!(
+            Style::Percent.format(de, "0.256", None).unwrap(),
+            "26\u{a0}%"
+        );
+        assert_eq!(
+            Style::DateLong.format(de, "2024-03-05", None).unwrap(),
+            "5. März 2024"
+        );
+        assert!(Style::Currency.format(de, "abc", None).is_none());
+    }
+}
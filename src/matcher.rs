@@ -0,0 +1,382 @@
+use alloc::{string::ToString, vec::Vec};
+use core::ops::Range;
+
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+#[cfg(not(feature = "std"))]
+use hashbrown::{HashMap, HashSet};
+
+use crate::error::{Error, Result};
+use crate::token_iterator::Token;
+use crate::Template;
+
+enum Segment<'t> {
+    Literal(&'t str),
+    Capture(&'t str),
+}
+
+impl<'t> Template<'t> {
+    /// Extracts the values of the template's placeholders from `input`, which
+    /// is expected to be the result of filling the template, such as a log
+    /// line or a file name.
+    ///
+    /// The template's text is used as anchors around each placeholder. When
+    /// several splits are possible, placeholders capture as little text as
+    /// possible from left to right. A placeholder appearing several times
+    /// must capture the same value every time.
+    ///
+    /// Matching takes polynomial time in the length of `input`, and memory
+    /// proportional to the states it explores rather than to the size of the
+    /// template times the length of `input`, so it can be used on untrusted
+    /// input. Each repeated placeholder name raises the degree of the
+    /// polynomial, since every value it can capture has to be tried against
+    /// its later appearances.
+    ///
+    /// Returns `Ok(None)` when `input` does not match the template, and
+    /// `Error::AdjacentPlaceholders` when two placeholders are not separated
+    /// by any text, since their values could be split in many ways.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// let template = Template::new("{{date}}-{{name}}.{{ext}}");
+    ///
+    /// let values = template.match_str("2024-01-01-report.tar.gz").unwrap().unwrap();
+    ///
+    /// assert_eq!(values["date"], "2024");
+    /// assert_eq!(values["name"], "01-01-report");
+    /// assert_eq!(values["ext"], "tar.gz");
+    ///
+    /// assert!(template.match_str("report").unwrap().is_none());
+    /// ```
+    pub fn match_str<'i>(&self, input: &'i str) -> Result<Option<HashMap<&'t str, &'i str>>> {
+        let segments = self.match_segments()?;
+        let mut matcher = Matcher::new(&segments, input);
+
+        if !matcher.matches(0, 0) {
+            return Ok(None);
+        }
+
+        Ok(Some(matcher.captures.into_iter().collect()))
+    }
+
+    /// Drops empty text tokens and rejects adjacent placeholders.
+    fn match_segments(&self) -> Result<Vec<Segment<'t>>> {
+        let mut segments: Vec<Segment<'t>> = Vec::new();
+
+        for token in &self.tokens {
+            match token {
+                Token::Text("") => {}
+                // Consecutive text tokens are kept apart, they are not
+                // always contiguous in the template (e.g. around comments).
+                Token::Text(text) => segments.push(Segment::Literal(text)),
+                Token::Placeholder(name, ..) => {
+                    if let Some(Segment::Capture(previous)) = segments.last() {
+                        return Err(Error::AdjacentPlaceholders {
+                            first: previous.to_string(),
+                            second: name.to_string(),
+                        });
+                    }
+
+                    segments.push(Segment::Capture(name));
+                }
+            }
+        }
+
+        Ok(segments)
+    }
+}
+
+/// Matches an input against the segments of a template, backtracking over
+/// the occurrences of the text following each placeholder.
+///
+/// States that failed to match are remembered, so each one is explored once:
+/// a state is a segment, an offset within the input, and the values of the
+/// names captured before the segment and used again from it. Without
+/// repeated names, matching is polynomial in the length of the input rather
+/// than exponential in the number of placeholders.
+struct Matcher<'s, 't, 'i> {
+    segments: &'s [Segment<'t>],
+    /// Names captured before each segment and used again from it.
+    shared: Vec<Vec<&'t str>>,
+    input: &'i str,
+    captures: Vec<(&'t str, &'i str)>,
+    /// Failed states without shared names, as segment and offset.
+    failures: HashSet<(usize, usize)>,
+    /// Failed states with shared names.
+    shared_failures: HashSet<(usize, usize, Vec<Range<usize>>)>,
+}
+
+impl<'s, 't, 'i> Matcher<'s, 't, 'i> {
+    fn new(segments: &'s [Segment<'t>], input: &'i str) -> Self {
+        // Appearances of each name from the current segment onward.
+        let mut remaining: HashMap<&'t str, usize> = HashMap::new();
+        for segment in segments {
+            if let Segment::Capture(name) = segment {
+                *remaining.entry(*name).or_default() += 1;
+            }
+        }
+
+        // Names captured before the current segment and used again from it.
+        let mut open: Vec<&'t str> = Vec::new();
+        let mut shared = Vec::with_capacity(segments.len() + 1);
+
+        for segment in segments {
+            shared.push(open.clone());
+
+            if let Segment::Capture(name) = segment {
+                let count = remaining.get_mut(name).unwrap();
+                *count -= 1;
+
+                match open.binary_search(name) {
+                    Ok(i) if *count == 0 => {
+                        open.remove(i);
+                    }
+                    Err(i) if *count > 0 => open.insert(i, name),
+                    _ => {}
+                }
+            }
+        }
+        shared.push(open);
+
+        Self {
+            segments,
+            shared,
+            input,
+            captures: Vec::new(),
+            failures: HashSet::new(),
+            shared_failures: HashSet::new(),
+        }
+    }
+
+    /// Matches the input from `offset` against the segments from `index`,
+    /// recording the captured values.
+    fn matches(&mut self, index: usize, offset: usize) -> bool {
+        if self.shared[index].is_empty() {
+            if self.failures.contains(&(index, offset)) {
+                return false;
+            }
+
+            let matched = self.step(index, offset);
+            if !matched {
+                self.failures.insert((index, offset));
+            }
+            return matched;
+        }
+
+        let key = (index, offset, self.shared_values(index));
+
+        if self.shared_failures.contains(&key) {
+            return false;
+        }
+
+        let matched = self.step(index, offset);
+        if !matched {
+            self.shared_failures.insert(key);
+        }
+
+        matched
+    }
+
+    fn step(&mut self, index: usize, offset: usize) -> bool {
+        let segments = self.segments;
+        let input = &self.input[offset..];
+
+        match &segments[index..] {
+            [] => input.is_empty(),
+            [Segment::Literal(literal), ..] => {
+                input.starts_with(literal) && self.matches(index + 1, offset + literal.len())
+            }
+            [Segment::Capture(name)] => capture(name, input, &mut self.captures),
+            [Segment::Capture(name), Segment::Literal(literal), ..] => {
+                // A name captured before can only match the same value again.
+                if let Some(value) = self.captured(name) {
+                    return input.starts_with(value)
+                        && input[value.len()..].starts_with(literal)
+                        && self.matches(index + 2, offset + value.len() + literal.len());
+                }
+
+                let mut search_from = 0;
+
+                while let Some(found) = input[search_from..].find(literal) {
+                    let end = search_from + found;
+
+                    self.captures.push((name, &input[..end]));
+                    if self.matches(index + 2, offset + end + literal.len()) {
+                        return true;
+                    }
+                    self.captures.pop();
+
+                    search_from = end + input[end..].chars().next().map_or(1, char::len_utf8);
+
+                    if search_from > input.len() {
+                        break;
+                    }
+                }
+
+                false
+            }
+            [Segment::Capture(_), Segment::Capture(_), ..] => false,
+        }
+    }
+
+    fn captured(&self, name: &str) -> Option<&'i str> {
+        self.captures
+            .iter()
+            .find(|(captured, _)| *captured == name)
+            .map(|(_, value)| *value)
+    }
+
+    /// Byte ranges within the input of the values of the names shared
+    /// around the segment at `index`.
+    fn shared_values(&self, index: usize) -> Vec<Range<usize>> {
+        self.shared[index]
+            .iter()
+            .filter_map(|name| self.captured(name))
+            .map(|value| {
+                let start = value.as_ptr() as usize - self.input.as_ptr() as usize;
+                start..start + value.len()
+            })
+            .collect()
+    }
+}
+
+/// Records `value` for `name`, unless `name` already captured another value.
+fn capture<'t, 'i>(name: &'t str, value: &'i str, captures: &mut Vec<(&'t str, &'i str)>) -> bool {
+    match captures.iter().find(|(captured, _)| *captured == name) {
+        Some((_, previous)) => *previous == value,
+        None => {
+            captures.push((name, value));
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        borrow::ToOwned,
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    use crate::Template;
+
+    #[test]
+    fn test_match_str() {
+        let template = Template::new("[{{level}}] {{message}} ({{file}}:{{line}})");
+        let values = template
+            .match_str("[error] could not open (file) (src/main.rs:42)")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(values.len(), 4);
+        assert_eq!(values["level"], "error");
+        assert_eq!(values["message"], "could not open");
+        assert_eq!(values["file"], "file) (src/main.rs");
+        assert_eq!(values["line"], "42");
+    }
+
+    #[test]
+    fn test_match_str_backtracks() {
+        let template = Template::new("{{a}}-{{b}}.txt");
+        let values = template.match_str("x-y.txt.txt").unwrap().unwrap();
+
+        assert_eq!(values["a"], "x");
+        assert_eq!(values["b"], "y.txt");
+    }
+
+    #[test]
+    fn test_match_str_no_match() {
+        let template = Template::new("hello {{name}}!");
+
+        assert!(template.match_str("goodbye world!").unwrap().is_none());
+        assert!(template.match_str("hello world").unwrap().is_none());
+        assert!(template.match_str("hello world!!").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_match_str_without_placeholders() {
+        let template = Template::new("hello world");
+
+        assert!(template
+            .match_str("hello world")
+            .unwrap()
+            .unwrap()
+            .is_empty());
+        assert!(template.match_str("hello").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_match_str_repeated_names_must_be_consistent() {
+        let template = Template::new("{{name}}/{{name}}.log");
+
+        assert_eq!(
+            template.match_str("a/b/a/b.log").unwrap().unwrap()["name"],
+            "a/b"
+        );
+        assert!(template.match_str("a/b.log").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_match_str_empty_values() {
+        let template = Template::new("{{a}}:{{b}}");
+        let values = template.match_str(":").unwrap().unwrap();
+
+        assert_eq!(values["a"], "");
+        assert_eq!(values["b"], "");
+    }
+
+    #[test]
+    fn test_match_str_multibyte() {
+        let template = Template::new("«{{a}}»{{b}}");
+        let values = template.match_str("«é»»ü").unwrap().unwrap();
+
+        assert_eq!(values["a"], "é");
+        assert_eq!(values["b"], "»ü");
+    }
+
+    #[test]
+    fn test_match_str_pathological_input() {
+        // Each placeholder can end at any of the spaces, which used to be
+        // explored again for every split of the previous placeholders.
+        let names: Vec<String> = (0..16).map(|i| format!("{{{{p{}}}}}", i)).collect();
+        let template_text = names.join(" ") + "!";
+        let template = Template::new(&template_text);
+        let input = " ".repeat(200);
+
+        assert!(template.match_str(&input).unwrap().is_none());
+        assert!(template
+            .match_str(&(input.clone() + "!"))
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_match_str_pathological_input_repeated() {
+        let repeated = Template::new("{{a}} {{b}} {{a}} {{c}} {{b}}!");
+        assert!(repeated.match_str(&" ".repeat(100)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_match_str_large_template_and_input() {
+        // Memory used to be allocated for every segment and offset upfront.
+        let names: Vec<String> = (0..4000).map(|i| format!("{{{{p{}}}}}", i)).collect();
+        let template_text = names.join("-");
+        let template = Template::new(&template_text);
+        let input = "x".repeat(1 << 20);
+
+        assert!(template.match_str(&input).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_match_str_adjacent_placeholders() {
+        let template = Template::new("{{first}}{{! comment }}{{second}}");
+
+        assert_eq!(
+            template.match_str("anything").map(|_| ()).map_err(|e| e.to_string()),
+            Err("Error while matching template. Reason: placeholders 'first' and 'second' are not separated by any text.".to_owned())
+        );
+    }
+}
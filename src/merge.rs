@@ -0,0 +1,250 @@
+use std::borrow::Cow;
+use std::io::Read;
+use std::sync::Arc;
+
+use csv::{ReaderBuilder, StringRecord, StringRecordsIntoIter, Trim};
+
+use crate::context::PlaceholderContext;
+use crate::error::{Error, Result};
+use crate::Template;
+
+/// A row of a CSV file, used as a context whose keys are the headers of the
+/// file. Available with the `csv` feature.
+pub struct CsvRow {
+    headers: Arc<StringRecord>,
+    record: StringRecord,
+}
+
+impl CsvRow {
+    /// Returns the value of the column named `header`. When several columns
+    /// have the same header the first one is used.
+    pub fn get(&self, header: &str) -> Option<&str> {
+        let index = self.headers.iter().position(|h| h == header)?;
+        self.record.get(index)
+    }
+
+    /// Returns the line of the row within the CSV file, starting at 1.
+    pub fn line(&self) -> Option<u64> {
+        self.record.position().map(|position| position.line())
+    }
+}
+
+impl PlaceholderContext for CsvRow {
+    fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+        CsvRow::get(self, key).map(Cow::Borrowed)
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.headers.iter().collect()
+    }
+}
+
+/// Iterator over the rows of a CSV file, created with [`CsvRows::new`].
+pub struct CsvRows<R> {
+    headers: Arc<StringRecord>,
+    records: StringRecordsIntoIter<R>,
+}
+
+impl<R: Read> CsvRows<R> {
+    /// Reads the header row of `reader`, whose values are the keys of the
+    /// following rows. Surrounding whitespace is trimmed from the headers.
+    ///
+    /// Failing to read the header row results in an `Error::Csv`.
+    pub fn new(reader: R) -> Result<Self> {
+        let mut reader = ReaderBuilder::new().trim(Trim::Headers).from_reader(reader);
+        let headers = Arc::new(reader.headers()?.clone());
+
+        Ok(Self {
+            headers,
+            records: reader.into_records(),
+        })
+    }
+
+    /// Returns the headers of the file.
+    pub fn headers(&self) -> Vec<&str> {
+        self.headers.iter().collect()
+    }
+}
+
+impl<R: Read> Iterator for CsvRows<R> {
+    type Item = Result<CsvRow>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = self.records.next()?;
+
+        Some(record.map_err(Error::from).map(|record| CsvRow {
+            headers: Arc::clone(&self.headers),
+            record,
+        }))
+    }
+}
+
+/// A document rendered from a row by [`Template::merge_csv_with_names`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MergedDocument {
+    /// The file name template filled with the row.
+    pub name: String,
+    /// The template filled with the row.
+    pub text: String,
+}
+
+impl<'t> Template<'t> {
+    /// Renders the template once per row of the CSV file read from `reader`,
+    /// using the header row as keys. Available with the `csv` feature.
+    ///
+    /// Each row behaves like in [`Template::render_many`]: a row that cannot
+    /// be read or that misses a placeholder results in an error for that row
+    /// only. Failing to read the header row results in an `Error::Csv`.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// let csv = "name,city\nAna,Lisbon\nBo,\n";
+    /// let template = Template::new("Dear {{name}} from {{city}}");
+    ///
+    /// let results = template.merge_csv(csv.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(results[0].as_deref().unwrap(), "Dear Ana from Lisbon");
+    /// assert_eq!(results[1].as_deref().unwrap(), "Dear Bo from ");
+    /// ```
+    pub fn merge_csv<R: Read>(&self, reader: R) -> Result<Vec<Result<String>>> {
+        let rows = CsvRows::new(reader)?;
+
+        Ok(rows
+            .map(|row| row.and_then(|row| self.fill_with_context_strict(&row)))
+            .collect())
+    }
+
+    /// Version of [`Template::merge_csv`] that also fills the `names`
+    /// template with each row, usually to name the file of each document.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// let csv = "id,name\n1,Ana\n2,Bo\n";
+    /// let template = Template::new("Dear {{name}}");
+    /// let names = Template::new("letters/{{id}}-{{name}}.txt");
+    ///
+    /// let documents = template.merge_csv_with_names(&names, csv.as_bytes()).unwrap();
+    /// let first = documents[0].as_ref().unwrap();
+    ///
+    /// assert_eq!(first.name, "letters/1-Ana.txt");
+    /// assert_eq!(first.text, "Dear Ana");
+    /// ```
+    pub fn merge_csv_with_names<R: Read>(
+        &self,
+        names: &Template<'_>,
+        reader: R,
+    ) -> Result<Vec<Result<MergedDocument>>> {
+        let rows = CsvRows::new(reader)?;
+
+        Ok(rows
+            .map(|row| {
+                let row = row?;
+
+                Ok(MergedDocument {
+                    name: names.fill_with_context_strict(&row)?,
+                    text: self.fill_with_context_strict(&row)?,
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CsvRows, MergedDocument};
+    use crate::{Error, Template};
+
+    #[test]
+    fn test_csv_rows() {
+        let csv = " name , city,name\nAna,\"Lisbon, PT\",Other\n";
+        let mut rows = CsvRows::new(csv.as_bytes()).unwrap();
+
+        assert_eq!(rows.headers(), ["name", "city", "name"]);
+
+        let row = rows.next().unwrap().unwrap();
+        assert_eq!(row.get("name"), Some("Ana"));
+        assert_eq!(row.get("city"), Some("Lisbon, PT"));
+        assert_eq!(row.get("zip"), None);
+        assert_eq!(row.line(), Some(2));
+
+        assert!(rows.next().is_none());
+    }
+
+    #[test]
+    fn test_merge_csv() {
+        let csv = "name,city\nAna,Lisbon\nBo,Porto\n";
+        let template = Template::new("{{name}} ({{city}})");
+
+        let results: Vec<_> = template
+            .merge_csv(csv.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(results, ["Ana (Lisbon)", "Bo (Porto)"]);
+    }
+
+    #[test]
+    fn test_merge_csv_row_errors() {
+        let csv = "name,city\nAna,Lisbon\nBo\nCy,Faro\n";
+        let template = Template::new("{{name}} ({{city}}) {{zip}}");
+
+        let results = template.merge_csv(csv.as_bytes()).unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(&results[0], Err(Error::MissingValue { name, .. }) if name == "zip"));
+        assert!(matches!(&results[1], Err(Error::Csv(_))));
+        assert!(matches!(&results[2], Err(Error::MissingValue { .. })));
+    }
+
+    #[test]
+    fn test_merge_csv_with_names() {
+        let csv = "id,name\n1,Ana\n2,Bo\n";
+        let template = Template::new("Dear {{name}}");
+        let names = Template::new("{{id}}.txt");
+
+        let documents: Vec<_> = template
+            .merge_csv_with_names(&names, csv.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(
+            documents,
+            [
+                MergedDocument {
+                    name: "1.txt".to_string(),
+                    text: "Dear Ana".to_string(),
+                },
+                MergedDocument {
+                    name: "2.txt".to_string(),
+                    text: "Dear Bo".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_csv_with_names_missing_value() {
+        let csv = "name\nAna\n";
+        let names = Template::new("{{id}}.txt");
+
+        let documents = Template::new("{{name}}")
+            .merge_csv_with_names(&names, csv.as_bytes())
+            .unwrap();
+
+        assert!(matches!(&documents[0], Err(Error::MissingValue { name, .. }) if name == "id"));
+    }
+
+    #[test]
+    fn test_merge_csv_empty() {
+        let template = Template::new("{{name}}");
+
+        assert!(template.merge_csv("".as_bytes()).unwrap().is_empty());
+        assert!(template.merge_csv("name\n".as_bytes()).unwrap().is_empty());
+    }
+}
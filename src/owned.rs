@@ -0,0 +1,213 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ops::Range;
+
+use crate::error::Result;
+use crate::format::FormatSpec;
+use crate::token_iterator::{span_of, Token};
+use crate::{Template, DEFAULT_END_PLACEHOLDER, DEFAULT_START_PLACEHOLDER};
+
+enum OwnedToken {
+    Text(Range<usize>),
+    Placeholder(Range<usize>, usize, Option<FormatSpec>),
+}
+
+/// A parsed template that owns its text, so it can be stored without
+/// borrowing, such as in a registry of templates loaded from files.
+///
+/// Tokens are kept as byte ranges of the text, and [`OwnedTemplate::template`]
+/// borrows them as a [`Template`] without parsing the text again.
+///
+/// Example:
+/// ```rust
+/// # use text_placeholder::OwnedTemplate;
+/// # #[cfg(feature = "std")]
+/// # use std::collections::HashMap;
+/// # #[cfg(not(feature = "std"))]
+/// # use hashbrown::HashMap;
+/// let template = OwnedTemplate::new(String::from("Hello {{name}}!"));
+/// let table = HashMap::from([("name", "Ana")]);
+///
+/// assert_eq!(template.template().fill_with_hashmap(&table), "Hello Ana!");
+/// ```
+pub struct OwnedTemplate {
+    source: String,
+    tokens: Vec<OwnedToken>,
+    delimiters: Vec<(String, String)>,
+}
+
+impl OwnedTemplate {
+    /// Owned version of [`Template::new`].
+    pub fn new(text: String) -> Self {
+        Self::new_with_placeholder(text, DEFAULT_START_PLACEHOLDER, DEFAULT_END_PLACEHOLDER)
+    }
+
+    /// Owned version of [`Template::new_with_placeholder`].
+    pub fn new_with_placeholder(text: String, start: &str, end: &str) -> Self {
+        let owned = Template::new_with_placeholder(&text, start, end).parts();
+        owned.with_source(text)
+    }
+
+    /// Owned version of [`Template::new_strict`].
+    pub fn new_strict(text: String) -> Result<Self> {
+        Self::new_with_placeholder_strict(text, DEFAULT_START_PLACEHOLDER, DEFAULT_END_PLACEHOLDER)
+    }
+
+    /// Owned version of [`Template::new_with_placeholder_strict`].
+    pub fn new_with_placeholder_strict(text: String, start: &str, end: &str) -> Result<Self> {
+        let owned = Template::new_with_placeholder_strict(&text, start, end)?.parts();
+        Ok(owned.with_source(text))
+    }
+
+    /// Returns the text the template was parsed from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Borrows the template as a [`Template`] in order to fill it.
+    pub fn template(&self) -> Template<'_> {
+        Template {
+            source: &self.source,
+            tokens: self
+                .tokens
+                .iter()
+                .map(|token| match token {
+                    OwnedToken::Text(range) => Token::Text(&self.source[range.clone()]),
+                    OwnedToken::Placeholder(range, delimiters, spec) => {
+                        Token::Placeholder(&self.source[range.clone()], *delimiters, *spec)
+                    }
+                })
+                .collect(),
+            delimiters: self
+                .delimiters
+                .iter()
+                .map(|(start, end)| (start.as_str(), end.as_str()))
+                .collect(),
+        }
+    }
+}
+
+impl Template<'_> {
+    /// Copies the template into an [`OwnedTemplate`], which does not borrow
+    /// the text.
+    pub fn to_owned_template(&self) -> OwnedTemplate {
+        self.parts().with_source(self.source.to_string())
+    }
+
+    /// Converts the tokens into ranges of the source text, leaving the text
+    /// to the caller.
+    fn parts(&self) -> OwnedTemplate {
+        // Empty slices may not point into the source text.
+        let range_of = |s: &str| {
+            if s.is_empty() {
+                0..0
+            } else {
+                span_of(self.source, s)
+            }
+        };
+
+        OwnedTemplate {
+            source: String::new(),
+            tokens: self
+                .tokens
+                .iter()
+                .map(|token| match token {
+                    Token::Text(s) => OwnedToken::Text(range_of(s)),
+                    Token::Placeholder(s, delimiters, spec) => {
+                        OwnedToken::Placeholder(range_of(s), *delimiters, *spec)
+                    }
+                })
+                .collect(),
+            delimiters: self
+                .delimiters
+                .iter()
+                .map(|(start, end)| (start.to_string(), end.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl OwnedTemplate {
+    fn with_source(mut self, source: String) -> Self {
+        self.source = source;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        borrow::ToOwned,
+        string::{String, ToString},
+    };
+
+    #[cfg(feature = "std")]
+    use std::collections::HashMap;
+
+    #[cfg(not(feature = "std"))]
+    use hashbrown::HashMap;
+
+    use super::OwnedTemplate;
+    use crate::Template;
+
+    #[test]
+    fn test_owned_template() {
+        let template = OwnedTemplate::new(
+            "{{! note }}Hello {{~ name ~}} , {{#raw}}{{x}}{{/raw}} {{=[ ]=}}[name]!".to_owned(),
+        );
+
+        let mut table = HashMap::new();
+        table.insert("name", "Ana");
+
+        assert_eq!(
+            template.template().fill_with_hashmap(&table),
+            "HelloAna, {{x}} Ana!"
+        );
+        assert_eq!(template.template().placeholders(), ["name"]);
+    }
+
+    #[test]
+    fn test_owned_template_format_specifiers() {
+        let template = OwnedTemplate::new("{{price:>6.2}}".to_owned());
+
+        let mut table = HashMap::new();
+        table.insert("price", "9.5");
+
+        assert_eq!(template.template().fill_with_hashmap(&table), "  9.50");
+    }
+
+    #[test]
+    fn test_owned_template_partial_fill_keeps_delimiters() {
+        let template = OwnedTemplate::new_with_placeholder("<a> <=[ ]=>[b]".to_owned(), "<", ">");
+
+        assert_eq!(
+            template
+                .template()
+                .fill_with_hashmap_partial(&HashMap::new()),
+            "<a> [b]"
+        );
+    }
+
+    #[test]
+    fn test_owned_template_strict() {
+        assert!(OwnedTemplate::new_strict("{{a}}".to_owned()).is_ok());
+        assert_eq!(
+            OwnedTemplate::new_strict("{{a".to_owned())
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Err("Error while parsing template. Reason: unclosed placeholder at 0..3.".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_to_owned_template() {
+        let text = String::from("{{a}} and {{b}}");
+        let owned = Template::new(&text).to_owned_template();
+        drop(text);
+
+        assert_eq!(owned.source(), "{{a}} and {{b}}");
+        assert_eq!(owned.template().placeholders(), ["a", "b"]);
+    }
+}
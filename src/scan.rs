@@ -0,0 +1,184 @@
+//! Searching for boundaries within the template text.
+//!
+//! Both searches look for the first byte of the boundaries and only compare
+//! the whole boundary on a candidate position. With the `memchr` feature the
+//! first byte is found with the SIMD routines of the `memchr` crate,
+//! otherwise a portable word-at-a-time search is used, which also works in
+//! `no_std` environments.
+
+use core::cmp::Reverse;
+
+/// Returns the byte index of the first occurrence of `needle` in `haystack`,
+/// following the semantics of [`str::find`].
+pub fn find(haystack: &str, needle: &str) -> Option<usize> {
+    let needle = needle.as_bytes();
+    let first = match needle.first() {
+        Some(first) => *first,
+        None => return Some(0),
+    };
+    let bytes = haystack.as_bytes();
+    let mut from = 0;
+
+    while let Some(offset) = find_byte(first, &bytes[from..]) {
+        let position = from + offset;
+
+        if bytes[position..].starts_with(needle) {
+            return Some(position);
+        }
+
+        from = position + 1;
+    }
+
+    None
+}
+
+/// Returns the byte index of the first occurrence of any of the `needles` in
+/// `haystack`, along with the index of the needle found. When several
+/// needles start at the same position the longest one wins. Empty needles
+/// are never found.
+pub fn find_any(haystack: &str, needles: &[&str]) -> Option<(usize, usize)> {
+    if let [needle] = needles {
+        if needle.is_empty() {
+            return None;
+        }
+
+        return find(haystack, needle).map(|position| (position, 0));
+    }
+
+    let mut first_bytes = [false; 256];
+    let mut distinct = [0u8; 3];
+    let mut distinct_len = 0;
+
+    for first in needles
+        .iter()
+        .filter_map(|needle| needle.as_bytes().first())
+    {
+        if !first_bytes[*first as usize] {
+            first_bytes[*first as usize] = true;
+
+            if distinct_len < distinct.len() {
+                distinct[distinct_len] = *first;
+            }
+            distinct_len += 1;
+        }
+    }
+
+    let bytes = haystack.as_bytes();
+    let mut from = 0;
+
+    loop {
+        let candidate = match distinct_len {
+            0 => None,
+            1 => find_byte(distinct[0], &bytes[from..]),
+            #[cfg(feature = "memchr")]
+            2 => memchr::memchr2(distinct[0], distinct[1], &bytes[from..]),
+            #[cfg(feature = "memchr")]
+            3 => memchr::memchr3(distinct[0], distinct[1], distinct[2], &bytes[from..]),
+            _ => bytes[from..]
+                .iter()
+                .position(|byte| first_bytes[*byte as usize]),
+        };
+
+        let position = from + candidate?;
+        let rest = &bytes[position..];
+        let found = needles
+            .iter()
+            .enumerate()
+            .filter(|(_, needle)| !needle.is_empty() && rest.starts_with(needle.as_bytes()))
+            .max_by_key(|(index, needle)| (needle.len(), Reverse(*index)));
+
+        if let Some((index, _)) = found {
+            return Some((position, index));
+        }
+
+        from = position + 1;
+    }
+}
+
+#[cfg(feature = "memchr")]
+fn find_byte(byte: u8, haystack: &[u8]) -> Option<usize> {
+    memchr::memchr(byte, haystack)
+}
+
+/// Portable version of `memchr`, comparing a whole word at a time.
+#[cfg(not(feature = "memchr"))]
+fn find_byte(byte: u8, haystack: &[u8]) -> Option<usize> {
+    const WORD: usize = core::mem::size_of::<usize>();
+    const LO: usize = usize::MAX / 255;
+    const HI: usize = LO << 7;
+
+    let repeated = LO * byte as usize;
+    let mut chunks = haystack.chunks_exact(WORD);
+    let mut offset = 0;
+
+    for chunk in &mut chunks {
+        let mut word = [0; WORD];
+        word.copy_from_slice(chunk);
+        // Bytes equal to `byte` become zero, which is detected by the
+        // classic "has zero byte" trick.
+        let word = usize::from_ne_bytes(word) ^ repeated;
+
+        if word.wrapping_sub(LO) & !word & HI != 0 {
+            return chunk
+                .iter()
+                .position(|b| *b == byte)
+                .map(|position| offset + position);
+        }
+
+        offset += WORD;
+    }
+
+    chunks
+        .remainder()
+        .iter()
+        .position(|b| *b == byte)
+        .map(|position| offset + position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, find_any};
+
+    #[test]
+    fn test_find() {
+        let haystack = "some text { {{ with a placeholder }} and some more text";
+
+        for needle in ["", "{{", "}}", "{", "text", "more text", "missing", "}}}"] {
+            assert_eq!(find(haystack, needle), haystack.find(needle), "{}", needle);
+        }
+    }
+
+    #[test]
+    fn test_find_every_offset() {
+        // Exercises both the word-at-a-time search and the remainder.
+        let haystack = "abcdefghijklmnopqrstuvwxyz0123456789";
+
+        for (position, c) in haystack.char_indices() {
+            let mut needle = [0; 4];
+            assert_eq!(find(haystack, c.encode_utf8(&mut needle)), Some(position));
+        }
+    }
+
+    #[test]
+    fn test_find_multibyte() {
+        let haystack = "héllo «wörld» «end»";
+
+        assert_eq!(find(haystack, "«"), haystack.find("«"));
+        assert_eq!(find(haystack, "»"), haystack.find("»"));
+    }
+
+    #[test]
+    fn test_find_any() {
+        let haystack = "text ${{placeholder}}$ {{other}} <%last%>";
+
+        assert_eq!(find_any(haystack, &["{{"]), Some((6, 0)));
+        assert_eq!(find_any(haystack, &["{{", "${"]), Some((5, 1)));
+        assert_eq!(find_any(haystack, &["${", "${{"]), Some((5, 1)));
+        assert_eq!(find_any(haystack, &["<%", "#"]), Some((33, 0)));
+        assert_eq!(find_any(haystack, &["#", "@", "?", "<%"]), Some((33, 3)));
+        assert_eq!(find_any(haystack, &["[", "("]), None);
+        assert_eq!(find_any(haystack, &["", "<%"]), Some((33, 1)));
+        assert_eq!(find_any(haystack, &[""]), None);
+        assert_eq!(find_any(haystack, &[]), None);
+    }
+}
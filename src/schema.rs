@@ -0,0 +1,248 @@
+use alloc::vec::Vec;
+
+use serde_json::{json, Map, Value};
+
+use crate::token_iterator::Token;
+use crate::Template;
+
+const JSON_SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A placeholder path segment, with the segments nested under it.
+struct Node<'t> {
+    name: &'t str,
+    /// Whether the placeholder ending at this segment is a number.
+    numeric: bool,
+    children: Vec<Node<'t>>,
+}
+
+impl<'t> Node<'t> {
+    fn insert(children: &mut Vec<Node<'t>>, path: &[&'t str], numeric: bool) {
+        let (first, rest) = match path.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+
+        let index = match children.iter().position(|node| node.name == *first) {
+            Some(index) => index,
+            None => {
+                children.push(Node {
+                    name: first,
+                    numeric: false,
+                    children: Vec::new(),
+                });
+                children.len() - 1
+            }
+        };
+
+        if rest.is_empty() {
+            children[index].numeric = numeric;
+        }
+
+        Node::insert(&mut children[index].children, rest, numeric);
+    }
+
+    fn object_schema(children: &[Node<'t>]) -> Map<String, Value> {
+        let properties: Map<String, Value> = children
+            .iter()
+            .map(|node| (node.name.into(), node.schema()))
+            .collect();
+        let required: Vec<&str> = children.iter().map(|node| node.name).collect();
+
+        let mut schema = Map::new();
+        schema.insert("type".into(), json!("object"));
+        schema.insert("properties".into(), Value::Object(properties));
+        schema.insert("required".into(), json!(required));
+        schema
+    }
+
+    fn schema(&self) -> Value {
+        if self.children.is_empty() {
+            json!({ "type": if self.numeric { "number" } else { "string" } })
+        } else {
+            Value::Object(Node::object_schema(&self.children))
+        }
+    }
+}
+
+impl<'t> Template<'t> {
+    /// Generates a JSON Schema describing a context that fills every
+    /// placeholder of the template, available with the `struct_context`
+    /// feature.
+    ///
+    /// Each placeholder becomes a required string property, or a number
+    /// property when every use of it has a numeric format spec such as
+    /// `{{price:.2}}`. Dotted names such as `user.name` become nested
+    /// objects, which is how [`Template::fill_with_struct_strict`] looks them
+    /// up, and when a name is used both as a value and as an object (`user`
+    /// and `user.name`) the object wins.
+    ///
+    /// Templates have neither loop sections nor default values, so the
+    /// schema has no arrays and every property is required.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// # use serde_json::json;
+    /// let template = Template::new("{{greeting}} {{user.first}} {{user.last}}!");
+    ///
+    /// assert_eq!(
+    ///     template.json_schema(),
+    ///     json!({
+    ///         "$schema": "https://json-schema.org/draft/2020-12/schema",
+    ///         "type": "object",
+    ///         "properties": {
+    ///             "greeting": { "type": "string" },
+    ///             "user": {
+    ///                 "type": "object",
+    ///                 "properties": {
+    ///                     "first": { "type": "string" },
+    ///                     "last": { "type": "string" }
+    ///                 },
+    ///                 "required": ["first", "last"]
+    ///             }
+    ///         },
+    ///         "required": ["greeting", "user"]
+    ///     })
+    /// );
+    /// ```
+    pub fn json_schema(&self) -> Value {
+        let mut root = Vec::new();
+
+        for placeholder in self.placeholders() {
+            let numeric = self.tokens.iter().all(|token| match token {
+                Token::Placeholder(name, _, spec) if *name == placeholder => {
+                    spec.is_some_and(|spec| spec.is_numeric())
+                }
+                _ => true,
+            });
+            let path: Vec<&str> = placeholder.split('.').collect();
+            Node::insert(&mut root, &path, numeric);
+        }
+
+        let mut schema = Map::new();
+        schema.insert("$schema".into(), json!(JSON_SCHEMA_DRAFT));
+        schema.extend(Node::object_schema(&root));
+
+        Value::Object(schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::Template;
+
+    #[test]
+    fn test_json_schema_flat() {
+        let template = Template::new("{{b}} {{a}} {{b}} {{! c }}");
+        let schema = template.json_schema();
+
+        assert_eq!(schema["type"], "object");
+        assert_eq!(
+            schema["properties"],
+            json!({ "a": { "type": "string" }, "b": { "type": "string" } })
+        );
+        assert_eq!(schema["required"], json!(["b", "a"]));
+    }
+
+    #[test]
+    fn test_json_schema_matching_value_fills_template() {
+        let template = Template::new("{{greeting}} {{user.first}} {{user.name.last}}!");
+        let schema = template.json_schema();
+        let context = json!({
+            "greeting": "Hello",
+            "user": { "first": "Ana", "name": { "last": "Silva" } }
+        });
+
+        assert_eq!(
+            schema["properties"]["user"]["properties"]["name"]["required"],
+            json!(["last"])
+        );
+        assert_eq!(
+            template.fill_with_struct_strict(&context).unwrap(),
+            "Hello Ana Silva!"
+        );
+        assert_eq!(
+            template.fill_with_struct(&context).unwrap(),
+            "Hello Ana Silva!"
+        );
+    }
+
+    #[test]
+    fn test_dotted_names_prefer_whole_keys() {
+        let template = Template::new("{{user.first}}");
+        let context = json!({ "user.first": "flat", "user": { "first": "nested" } });
+
+        assert_eq!(template.fill_with_struct_strict(&context).unwrap(), "flat");
+        assert!(template
+            .fill_with_struct_strict(&json!({ "user": "Ana" }))
+            .is_err());
+    }
+
+    #[test]
+    fn test_json_schema_numeric_specs() {
+        let template = Template::new(
+            "{{price:.2}} {{count:+}} {{id:6}} {{total:05}} {{total}} {{item.qty:.0}}",
+        );
+        let schema = template.json_schema();
+
+        assert_eq!(
+            schema["properties"],
+            json!({
+                "price": { "type": "number" },
+                "count": { "type": "number" },
+                "id": { "type": "string" },
+                "total": { "type": "string" },
+                "item": {
+                    "type": "object",
+                    "properties": { "qty": { "type": "number" } },
+                    "required": ["qty"]
+                }
+            })
+        );
+        assert_eq!(
+            template
+                .fill_with_struct_strict(&json!({
+                    "price": 3.5,
+                    "count": 2,
+                    "id": "7",
+                    "total": "9",
+                    "item": { "qty": 4 }
+                }))
+                .unwrap(),
+            "3.50 +2 7      00009 9 4"
+        );
+    }
+
+    #[test]
+    fn test_json_schema_without_placeholders() {
+        let schema = Template::new("text").json_schema();
+
+        assert_eq!(schema["properties"], json!({}));
+        assert_eq!(schema["required"], json!([]));
+    }
+
+    #[test]
+    fn test_json_schema_nested() {
+        let template = Template::new("{{a.b.c}} {{a.d}} {{a}} {{a.b}}");
+        let schema = template.json_schema();
+
+        assert_eq!(
+            schema["properties"]["a"],
+            json!({
+                "type": "object",
+                "properties": {
+                    "b": {
+                        "type": "object",
+                        "properties": { "c": { "type": "string" } },
+                        "required": ["c"]
+                    },
+                    "d": { "type": "string" }
+                },
+                "required": ["b", "d"]
+            })
+        );
+        assert_eq!(schema["required"], json!(["a"]));
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::SystemTime;
+
+use crate::error::{Error, Result};
+use crate::loader::{read_template, template_name, walk};
+use crate::owned::OwnedTemplate;
+use crate::{DEFAULT_END_PLACEHOLDER, DEFAULT_START_PLACEHOLDER};
+
+#[derive(Clone)]
+struct Entry {
+    path: PathBuf,
+    /// Modification time of the file when `template` was loaded.
+    modified: Option<SystemTime>,
+    /// Modification time of the last version that failed to load, so it is
+    /// not parsed again on every access.
+    failed: Option<SystemTime>,
+    template: Arc<OwnedTemplate>,
+}
+
+/// Templates loaded from a directory that are reloaded when their files
+/// change, available with the `std` feature.
+///
+/// Templates are named like in [`TemplateDir`](crate::TemplateDir). On every
+/// [`TemplateStore::get`] the modification time of the file is checked and
+/// the template is parsed again when it changed. New and removed files are
+/// picked up by [`TemplateStore::refresh`].
+///
+/// Templates are swapped atomically: a template returned by `get` is never
+/// modified, and when a new version fails to load the last good version is
+/// kept.
+///
+/// Example:
+/// ```rust,no_run
+/// # use text_placeholder::TemplateStore;
+/// # use std::collections::HashMap;
+/// let store = TemplateStore::open("templates").unwrap();
+///
+/// // Picks up the latest version of templates/welcome.txt.
+/// let welcome = store.get("welcome").unwrap();
+/// let text = welcome.template().fill_with_hashmap(&HashMap::from([("name", "Ana")]));
+/// ```
+pub struct TemplateStore {
+    dir: PathBuf,
+    start: String,
+    end: String,
+    entries: RwLock<HashMap<String, Entry>>,
+}
+
+impl TemplateStore {
+    /// Loads the templates under `dir` with boundaries specified by the
+    /// handlebars syntax, failing like [`TemplateDir::load`](crate::TemplateDir::load).
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        Self::open_with_placeholder(dir, DEFAULT_START_PLACEHOLDER, DEFAULT_END_PLACEHOLDER)
+    }
+
+    /// Loads the templates under `dir` with boundaries specified by the
+    /// `start` and `end` arguments, see [`TemplateStore::open`].
+    pub fn open_with_placeholder<P: AsRef<Path>>(dir: P, start: &str, end: &str) -> Result<Self> {
+        let store = Self {
+            dir: dir.as_ref().to_path_buf(),
+            start: start.into(),
+            end: end.into(),
+            entries: RwLock::new(HashMap::new()),
+        };
+        store.refresh()?;
+
+        Ok(store)
+    }
+
+    /// Returns the template named `name`, parsing its file again when it was
+    /// modified since it was loaded. When the new version cannot be read or
+    /// parsed, or the file was removed, the last good version is returned.
+    pub fn get(&self, name: &str) -> Option<Arc<OwnedTemplate>> {
+        let entry = self.read().get(name).cloned()?;
+        let modified = modified(&entry.path);
+
+        if modified.is_none() || modified == entry.modified || modified == entry.failed {
+            return Some(entry.template);
+        }
+
+        let result = read_template(&entry.path, &self.start, &self.end);
+        let mut entries = self.write();
+        // Only the version checked above is updated, so that a newer version
+        // or the removal of the template by `refresh` is not overwritten.
+        let current = entries
+            .get_mut(name)
+            .filter(|current| current.path == entry.path && current.modified == entry.modified);
+
+        match result {
+            Ok(template) => {
+                let template = Arc::new(template);
+                if let Some(current) = current {
+                    current.modified = modified;
+                    current.failed = None;
+                    current.template = Arc::clone(&template);
+                }
+
+                Some(template)
+            }
+            Err(_) => {
+                if let Some(current) = current {
+                    current.failed = modified;
+                }
+
+                Some(entry.template)
+            }
+        }
+    }
+
+    /// Walks the directory again, loading new and modified files and
+    /// dropping the templates whose files were removed.
+    ///
+    /// Every file that cannot be loaded is reported in an `Error::Load`, the
+    /// last good version of those templates is kept and the other templates
+    /// are still refreshed.
+    pub fn refresh(&self) -> Result<()> {
+        let mut files = Vec::new();
+        let mut errors = Vec::new();
+        walk(&self.dir, &mut files, &mut errors)?;
+        files.sort();
+
+        let snapshot = self.read().clone();
+        let mut entries = HashMap::new();
+
+        for path in files {
+            let relative = path.strip_prefix(&self.dir).unwrap_or(&path).to_path_buf();
+            let name = template_name(&relative);
+
+            if entries.contains_key(&name) {
+                errors.push((relative, Error::DuplicateTemplate { name }));
+                continue;
+            }
+
+            let modified = modified(&path);
+            let previous = snapshot.get(&name).filter(|entry| entry.path == path);
+
+            if let Some(entry) = previous {
+                if modified.is_some() && modified == entry.modified {
+                    entries.insert(name, entry.clone());
+                    continue;
+                }
+            }
+
+            match read_template(&path, &self.start, &self.end) {
+                Ok(template) => {
+                    let entry = Entry {
+                        path,
+                        modified,
+                        failed: None,
+                        template: Arc::new(template),
+                    };
+                    entries.insert(name, entry);
+                }
+                Err(err) => {
+                    if let Some(entry) = previous {
+                        let entry = Entry {
+                            failed: modified,
+                            ..entry.clone()
+                        };
+                        entries.insert(name, entry);
+                    }
+
+                    errors.push((relative, err));
+                }
+            }
+        }
+
+        let mut current = self.write();
+        for (name, entry) in &mut entries {
+            // Keeps the versions loaded by `get` while the files were read.
+            if let Some(newer) = current.get(name).filter(|newer| {
+                newer.path == entry.path
+                    && (newer.modified, newer.failed) > (entry.modified, entry.failed)
+            }) {
+                *entry = newer.clone();
+            }
+        }
+        *current = entries;
+        drop(current);
+        errors.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Load { errors })
+        }
+    }
+
+    /// Returns the names of the loaded templates, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.read().keys().cloned().collect();
+        names.sort_unstable();
+        names
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, Entry>> {
+        self.entries.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<String, Entry>> {
+        self.entries.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::time::{Duration, SystemTime};
+
+    use super::TemplateStore;
+    use crate::loader::tests::TempDir;
+    use crate::Error;
+
+    /// Writes `text` to `relative` with a modification time `seconds` after
+    /// the epoch, so changes are detected regardless of the file system
+    /// timestamp resolution.
+    fn write(dir: &TempDir, relative: &str, text: &str, seconds: u64) {
+        dir.write(relative, text);
+        File::options()
+            .write(true)
+            .open(dir.0.join(relative))
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+            .unwrap();
+    }
+
+    fn placeholders(store: &TemplateStore, name: &str) -> Vec<String> {
+        store
+            .get(name)
+            .unwrap()
+            .template()
+            .placeholders()
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_store_reloads_modified_files() {
+        let dir = TempDir::new("store_reload");
+        write(&dir, "a.txt", "{{first}}", 1_000);
+
+        let store = TemplateStore::open(&dir.0).unwrap();
+        let first = store.get("a").unwrap();
+
+        assert_eq!(placeholders(&store, "a"), ["first"]);
+
+        write(&dir, "a.txt", "{{second}}", 2_000);
+
+        assert_eq!(placeholders(&store, "a"), ["second"]);
+        // Templates handed out before are not modified.
+        assert_eq!(first.template().placeholders(), ["first"]);
+    }
+
+    #[test]
+    fn test_store_keeps_last_good_version() {
+        let dir = TempDir::new("store_last_good");
+        write(&dir, "a.txt", "{{first}}", 1_000);
+
+        let store = TemplateStore::open(&dir.0).unwrap();
+
+        write(&dir, "a.txt", "{{unclosed", 2_000);
+
+        assert_eq!(placeholders(&store, "a"), ["first"]);
+        assert!(matches!(store.refresh(), Err(Error::Load { errors }) if errors.len() == 1));
+        assert_eq!(placeholders(&store, "a"), ["first"]);
+
+        write(&dir, "a.txt", "{{fixed}}", 3_000);
+
+        assert_eq!(placeholders(&store, "a"), ["fixed"]);
+        assert!(store.refresh().is_ok());
+    }
+
+    #[test]
+    fn test_store_refresh_adds_and_removes_files() {
+        let dir = TempDir::new("store_refresh");
+        write(&dir, "a.txt", "{{a}}", 1_000);
+        write(&dir, "b.txt", "{{b}}", 1_000);
+
+        let store = TemplateStore::open(&dir.0).unwrap();
+
+        write(&dir, "nested/c.txt", "{{c}}", 1_000);
+        fs::remove_file(dir.0.join("b.txt")).unwrap();
+
+        assert!(store.get("nested/c").is_none());
+        // A removed file keeps its last good version until refreshed.
+        assert_eq!(placeholders(&store, "b"), ["b"]);
+
+        store.refresh().unwrap();
+
+        assert_eq!(store.names(), ["a", "nested/c"]);
+        assert_eq!(placeholders(&store, "nested/c"), ["c"]);
+        assert!(store.get("b").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_store_refresh_dangling_symlink() {
+        let dir = TempDir::new("store_dangling_symlink");
+        write(&dir, "a.txt", "{{a}}", 1_000);
+
+        let store = TemplateStore::open(&dir.0).unwrap();
+
+        write(&dir, "b.txt", "{{b}}", 1_000);
+        std::os::unix::fs::symlink("missing.txt", dir.0.join("broken.txt")).unwrap();
+
+        assert!(matches!(
+            store.refresh(),
+            Err(Error::Load { errors }) if errors.len() == 1 && matches!(errors[0].1, Error::Io { .. })
+        ));
+        assert_eq!(store.names(), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_store_open_errors() {
+        let dir = TempDir::new("store_open");
+        write(&dir, "a.txt", "{{unclosed", 1_000);
+
+        assert!(matches!(
+            TemplateStore::open(&dir.0),
+            Err(Error::Load { .. })
+        ));
+        assert!(matches!(
+            TemplateStore::open(dir.0.join("missing")),
+            Err(Error::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_store_concurrent_refresh_and_get() {
+        let dir = TempDir::new("store_concurrent");
+        write(&dir, "a.txt", "{{a}}", 1_000);
+        write(&dir, "b.txt", "{{b}}", 1_000);
+
+        let store = TemplateStore::open(&dir.0).unwrap();
+        // Slow to parse, so that `refresh` runs while `get` reloads it.
+        let large = "{{b}} ".repeat(200_000);
+
+        for round in 1..=5 {
+            write(&dir, "b.txt", &large, 1_000 + round);
+
+            std::thread::scope(|scope| {
+                scope.spawn(|| store.get("b"));
+
+                std::thread::sleep(Duration::from_millis(2));
+                write(&dir, "a.txt", "{{a}} {{c}}", 1_000 + round);
+                fs::remove_file(dir.0.join("b.txt")).unwrap();
+                store.refresh().unwrap();
+            });
+
+            // The reload by `get` does not resurrect the removed template.
+            assert!(store.get("b").is_none());
+            assert_eq!(store.names(), ["a"]);
+            assert_eq!(placeholders(&store, "a"), ["a", "c"]);
+
+            write(&dir, "b.txt", "{{b}}", 1_000);
+            store.refresh().unwrap();
+        }
+    }
+}
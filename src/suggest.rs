@@ -0,0 +1,93 @@
+//! Suggestions of similar names for missing placeholders.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Returns the `keys` close enough to `name` to be a likely typo, closest
+/// first. A key is close enough when its edit distance to `name` is at most
+/// a third of the length of `name`, and at least one.
+pub fn suggestions<'k, I>(name: &str, keys: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'k str>,
+{
+    let max_distance = (name.chars().count() / 3).max(1);
+    let mut candidates: Vec<(usize, &str)> = keys
+        .into_iter()
+        .filter(|key| *key != name)
+        .filter_map(|key| {
+            let distance = edit_distance(name, key);
+            (distance <= max_distance).then_some((distance, key))
+        })
+        .collect();
+
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates
+        .into_iter()
+        .map(|(_, key)| key.to_string())
+        .collect()
+}
+
+/// Edit distance between `a` and `b` counted in chars, where an insertion, a
+/// deletion, a substitution or a swap of two adjacent chars costs one.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut before_previous = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 0..a.len() {
+        current[0] = i + 1;
+
+        for j in 0..b.len() {
+            let substitution = previous[j] + usize::from(a[i] != b[j]);
+            let mut distance = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+
+            if i > 0 && j > 0 && a[i] == b[j - 1] && a[i - 1] == b[j] {
+                distance = distance.min(before_previous[j - 1] + 1);
+            }
+
+            current[j + 1] = distance;
+        }
+
+        core::mem::swap(&mut before_previous, &mut previous);
+        core::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_distance, suggestions};
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("name", ""), 4);
+        assert_eq!(edit_distance("", "name"), 4);
+        assert_eq!(edit_distance("name", "name"), 0);
+        assert_eq!(edit_distance("usrname", "username"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("café", "cafe"), 1);
+        assert_eq!(edit_distance("emial", "email"), 1);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let keys = ["username", "user", "email", "usernames", "first_name"];
+
+        assert_eq!(suggestions("usrname", keys), ["username", "usernames"]);
+        assert_eq!(suggestions("emial", keys), ["email"]);
+        assert!(suggestions("address", keys).is_empty());
+    }
+
+    #[test]
+    fn test_suggestions_short_names() {
+        assert_eq!(suggestions("id", ["ip", "idx", "name"]), ["idx", "ip"]);
+        assert!(suggestions("x", ["x"]).is_empty());
+    }
+}
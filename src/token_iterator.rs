@@ -1,3 +1,28 @@
+use alloc::{string::ToString, vec::Vec};
+use core::ops::Range;
+
+use crate::error::Error;
+use crate::format::{self, FormatSpec};
+use crate::scan;
+use crate::validation::NameRules;
+
+/// Marker placed right after the starting boundary, or right before the
+/// closing boundary, to strip the whitespace of the adjacent text.
+const TRIM_MARKER: char = '~';
+
+/// Marker starting a comment such as `{{! note }}`, which is not rendered.
+const COMMENT_MARKER: char = '!';
+
+/// Names of the tags delimiting a raw block such as `{{#raw}}...{{/raw}}`,
+/// whose contents are emitted as text without parsing placeholders. The
+/// prefixes keep a placeholder named `raw` usable.
+const RAW_OPEN: &str = "#raw";
+const RAW_CLOSE: &str = "/raw";
+
+/// Marker surrounding a set-delimiter tag such as `{{=<% %>=}}`, which
+/// changes the boundaries for the remainder of the text.
+const SET_DELIMITER_MARKER: char = '=';
+
 enum State {
     Text,
     Placeholder,
@@ -6,31 +31,129 @@ enum State {
 #[derive(Clone, PartialEq, Debug)]
 pub enum Token<'t> {
     Text(&'t str),
-    Placeholder(&'t str),
+    /// A named placeholder along with the index of the pair of boundaries
+    /// that produced it, see [`TokenIterator::delimiters`], and its format
+    /// spec such as `>10.2` in `{{price:>10.2}}`.
+    Placeholder(&'t str, usize, Option<FormatSpec>),
+}
+
+/// A tag delimited by the boundaries, such as `{{~ name }}`.
+struct Tag<'t> {
+    /// Everything between the boundaries and trim markers.
+    inner: &'t str,
+    /// `inner` without surrounding whitespace.
+    name: &'t str,
+    trim_before: bool,
+    trim_after: bool,
+    /// Length of the whole tag, boundaries included.
+    len: usize,
 }
 
 pub struct TokenIterator<'t> {
+    source: &'t str,
     text: &'t str,
     state: State,
-    start: &'t str,
-    end: &'t str,
+    /// Every pair of boundaries seen so far, including the ones introduced
+    /// by set-delimiter tags.
+    delimiters: Vec<(&'t str, &'t str)>,
+    /// Indexes within `delimiters` of the pairs currently recognized.
+    active: Vec<usize>,
+    /// Starting boundaries of the `active` pairs, in the same order.
+    starts: Vec<&'t str>,
+    /// Index within `delimiters` of the pair that opened the current tag.
+    current: usize,
+    rules: Option<NameRules>,
+    error: Option<Error>,
+}
+
+/// Byte range of `s` within `source`, `s` must be a slice of `source`.
+pub(crate) fn span_of(source: &str, s: &str) -> Range<usize> {
+    let start = s.as_ptr() as usize - source.as_ptr() as usize;
+    start..start + s.len()
 }
 
 impl<'t> TokenIterator<'t> {
     pub fn new(text: &'t str, start: &'t str, end: &'t str) -> Self {
+        Self::with_delimiters(text, &[(start, end)])
+    }
+
+    /// Creates an iterator recognizing several pairs of boundaries at once,
+    /// whichever starting boundary appears first opens the next placeholder.
+    pub fn with_delimiters(text: &'t str, delimiters: &[(&'t str, &'t str)]) -> Self {
         Self {
+            source: text,
             text,
-            start,
-            end,
             state: State::Text,
+            delimiters: delimiters.to_vec(),
+            active: (0..delimiters.len()).collect(),
+            starts: delimiters.iter().map(|(start, _)| *start).collect(),
+            current: 0,
+            rules: None,
+            error: None,
         }
     }
 
+    /// Every pair of boundaries seen so far, indexed by the placeholder tokens.
+    pub fn delimiters(&self) -> &[(&'t str, &'t str)] {
+        &self.delimiters
+    }
+
+    fn start(&self) -> &'t str {
+        self.delimiters[self.current].0
+    }
+
+    fn end(&self) -> &'t str {
+        self.delimiters[self.current].1
+    }
+
+    /// Enables strict parsing: placeholder names are validated against
+    /// `rules` and unclosed placeholders are rejected. The iteration stops
+    /// at the first error, which can be retrieved with
+    /// [`TokenIterator::take_error`].
+    pub fn strict(mut self, rules: NameRules) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Returns the error that stopped a strict iteration, if any.
+    pub fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+
+    /// Byte range of `s`, which must be a slice of the source text.
+    fn span_of(&self, s: &str) -> Range<usize> {
+        span_of(self.source, s)
+    }
+
+    /// The whole text being tokenized.
+    pub fn source(&self) -> &'t str {
+        self.source
+    }
+
+    /// Finds the first starting boundary within the remaining text, along
+    /// with the index of its pair. When several boundaries start at the same
+    /// position the longest one wins.
+    fn find_start(&self) -> Option<(usize, usize)> {
+        scan::find_any(self.text, &self.starts)
+            .map(|(position, index)| (position, self.active[index]))
+    }
+
     fn parse_text(&mut self) -> Token<'t> {
         let token: Token;
 
-        if let Some(placeholder_index) = self.text.find(self.start) {
-            token = Token::Text(&self.text[..placeholder_index]);
+        if let Some((placeholder_index, current)) = self.find_start() {
+            self.current = current;
+            let mut text = &self.text[..placeholder_index];
+
+            // Unclosed tags are kept as text along with the whitespace
+            // before them.
+            if self.text[placeholder_index + self.start().len()..].starts_with(TRIM_MARKER)
+                && self.parse_tag(&self.text[placeholder_index..]).is_some()
+            {
+                text = text.trim_end();
+            }
+
+            token = Token::Text(text);
             self.text = &self.text[placeholder_index..];
             self.state = State::Placeholder;
         } else {
@@ -41,24 +164,176 @@ impl<'t> TokenIterator<'t> {
         token
     }
 
-    fn parse_placeholder(&mut self) -> Token<'t> {
-        let token: Token;
+    /// Parses the tag at the beginning of `text`, which must start with the
+    /// starting boundary. Returns `None` when the closing boundary is missing.
+    fn parse_tag(&self, text: &'t str) -> Option<Tag<'t>> {
+        let placeholder_index = scan::find(text, self.end())?;
+        let mut inner = &text[self.start().len()..placeholder_index];
+        let trim_before = inner.starts_with(TRIM_MARKER);
+        let trim_after = inner.len() > 1 && inner.ends_with(TRIM_MARKER);
+
+        if trim_before {
+            inner = &inner[TRIM_MARKER.len_utf8()..];
+        }
+        if trim_after {
+            inner = &inner[..inner.len() - TRIM_MARKER.len_utf8()];
+        }
+
+        Some(Tag {
+            inner,
+            name: inner.trim(),
+            trim_before,
+            trim_after,
+            len: placeholder_index + self.end().len(),
+        })
+    }
+
+    /// Moves past a tag, stripping the whitespace that follows it when the
+    /// tag ends with a trim marker.
+    fn skip_tag(&mut self, text: &'t str, tag: &Tag<'t>) {
+        self.text = &text[tag.len..];
+
+        if tag.trim_after {
+            self.text = self.text.trim_start();
+        }
+    }
+
+    /// Returns `None` when no token was produced, either because the
+    /// placeholder was a comment or because strict parsing failed.
+    fn parse_placeholder(&mut self) -> Option<Token<'t>> {
         self.state = State::Text;
 
-        if let Some(placeholder_index) = self.text.find(self.end) {
-            token = Token::Placeholder(
-                self.text[self.start.len()..placeholder_index]
-                    .trim_start_matches(' ')
-                    .trim_end_matches(' '),
-            );
-            let new_position = placeholder_index + self.end.len();
-            self.text = &self.text[new_position..];
-        } else {
-            token = Token::Text(self.text);
-            self.text = "";
+        let tag = match self.parse_tag(self.text) {
+            Some(tag) => tag,
+            None => return self.unclosed(self.text),
+        };
+
+        if tag.name.starts_with(COMMENT_MARKER) {
+            self.skip_tag(self.text, &tag);
+            return None;
         }
 
-        token
+        if tag.name == RAW_OPEN {
+            return self.parse_raw(tag);
+        }
+
+        if tag.name.len() > 1
+            && tag.name.starts_with(SET_DELIMITER_MARKER)
+            && tag.name.ends_with(SET_DELIMITER_MARKER)
+        {
+            return self.parse_set_delimiter(tag);
+        }
+
+        let (name, spec) = format::split(tag.name);
+
+        if let Some(rules) = &self.rules {
+            if let Err(reason) = rules.check(name, self.start(), self.end()) {
+                // Empty names point to everything between the boundaries.
+                let span = if name.is_empty() {
+                    self.span_of(tag.inner)
+                } else {
+                    self.span_of(name)
+                };
+
+                self.error = Some(Error::InvalidName {
+                    name: name.to_string(),
+                    span,
+                    reason,
+                });
+                return None;
+            }
+        }
+
+        self.skip_tag(self.text, &tag);
+        Some(Token::Placeholder(name, self.current, spec))
+    }
+
+    /// Emits everything between `open` and the matching closing raw tag as
+    /// a single text token, regardless of the boundaries within it.
+    fn parse_raw(&mut self, open: Tag<'t>) -> Option<Token<'t>> {
+        let block = self.text;
+        let mut content = &block[open.len..];
+
+        if open.trim_after {
+            content = content.trim_start();
+        }
+
+        let mut search_from = 0;
+
+        while let Some(index) = scan::find(&content[search_from..], self.start()) {
+            let close_index = search_from + index;
+            let rest = &content[close_index..];
+
+            match self.parse_tag(rest) {
+                Some(close) if close.name == RAW_CLOSE => {
+                    let mut raw = &content[..close_index];
+
+                    if close.trim_before {
+                        raw = raw.trim_end();
+                    }
+
+                    self.skip_tag(rest, &close);
+                    return Some(Token::Text(raw));
+                }
+                _ => search_from = close_index + self.start().len(),
+            }
+        }
+
+        self.unclosed(block)
+    }
+
+    /// Replaces the pair of boundaries that opened a tag such as
+    /// `{{=<% %>=}}` with the pair it contains. Invalid pairs are kept as
+    /// text, or rejected when parsing strictly.
+    fn parse_set_delimiter(&mut self, tag: Tag<'t>) -> Option<Token<'t>> {
+        let marker_len = SET_DELIMITER_MARKER.len_utf8();
+        let pair = &tag.name[marker_len..tag.name.len() - marker_len];
+        let mut delimiters = pair.split_whitespace();
+
+        match (delimiters.next(), delimiters.next(), delimiters.next()) {
+            (Some(start), Some(end), None) if start != end => {
+                self.skip_tag(self.text, &tag);
+
+                let replaced = self.current;
+                self.delimiters.push((start, end));
+
+                for (index, active) in self.active.iter_mut().enumerate() {
+                    if *active == replaced {
+                        *active = self.delimiters.len() - 1;
+                        self.starts[index] = start;
+                    }
+                }
+
+                None
+            }
+            _ => {
+                if self.rules.is_some() {
+                    self.error = Some(Error::InvalidDelimiters {
+                        span: self.span_of(tag.name),
+                    });
+                    return None;
+                }
+
+                let text = &self.text[..tag.len];
+                self.skip_tag(self.text, &tag);
+                Some(Token::Text(text))
+            }
+        }
+    }
+
+    /// Handles a placeholder or block starting at `text` that is never
+    /// closed: it is kept as text, or rejected when parsing strictly.
+    fn unclosed(&mut self, text: &'t str) -> Option<Token<'t>> {
+        self.text = "";
+
+        if self.rules.is_some() {
+            self.error = Some(Error::Unclosed {
+                span: self.span_of(text),
+            });
+            return None;
+        }
+
+        Some(Token::Text(text))
     }
 }
 
@@ -66,13 +341,19 @@ impl<'t> Iterator for TokenIterator<'t> {
     type Item = Token<'t>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.text.is_empty() {
-            return None;
-        }
+        loop {
+            if self.text.is_empty() || self.error.is_some() {
+                return None;
+            }
 
-        match self.state {
-            State::Text => Some(self.parse_text()),
-            State::Placeholder => Some(self.parse_placeholder()),
+            let token = match self.state {
+                State::Text => Some(self.parse_text()),
+                State::Placeholder => self.parse_placeholder(),
+            };
+
+            if token.is_some() {
+                return token;
+            }
         }
     }
 }
@@ -80,6 +361,8 @@ impl<'t> Iterator for TokenIterator<'t> {
 #[cfg(test)]
 mod tests {
     use super::{Token, TokenIterator};
+    use crate::error::Error;
+    use crate::validation::{InvalidNameReason, NameRules};
     extern crate alloc;
     use alloc::vec::Vec;
 
@@ -96,7 +379,7 @@ mod tests {
             tokens,
             vec![
                 Token::Text(""),
-                Token::Placeholder("placeholder"),
+                Token::Placeholder("placeholder", 0, None),
                 Token::Text(" text")
             ]
         );
@@ -109,7 +392,7 @@ mod tests {
             tokens,
             vec![
                 Token::Text("text "),
-                Token::Placeholder("placeholder"),
+                Token::Placeholder("placeholder", 0, None),
                 Token::Text(" text")
             ]
         );
@@ -120,7 +403,10 @@ mod tests {
         let tokens: Vec<Token> = TokenIterator::new("text [placeholder]", "[", "]").collect();
         assert_eq!(
             tokens,
-            vec![Token::Text("text "), Token::Placeholder("placeholder")]
+            vec![
+                Token::Text("text "),
+                Token::Placeholder("placeholder", 0, None)
+            ]
         );
     }
 
@@ -136,11 +422,11 @@ mod tests {
             tokens,
             vec![
                 Token::Text(""),
-                Token::Placeholder("placeholder"),
+                Token::Placeholder("placeholder", 0, None),
                 Token::Text(" text "),
-                Token::Placeholder("placeholder"),
+                Token::Placeholder("placeholder", 0, None),
                 Token::Text(" test "),
-                Token::Placeholder("placeholder")
+                Token::Placeholder("placeholder", 0, None)
             ]
         );
     }
@@ -168,7 +454,7 @@ mod tests {
             tokens,
             vec![
                 Token::Text("text "),
-                Token::Placeholder("placeholder"),
+                Token::Placeholder("placeholder", 0, None),
                 Token::Text(" "),
                 Token::Text("[placeholder")
             ]
@@ -182,7 +468,7 @@ mod tests {
             tokens,
             vec![
                 Token::Text(""),
-                Token::Placeholder("placeholder"),
+                Token::Placeholder("placeholder", 0, None),
                 Token::Text(" text")
             ]
         );
@@ -196,7 +482,7 @@ mod tests {
             tokens,
             vec![
                 Token::Text("text "),
-                Token::Placeholder("placeholder"),
+                Token::Placeholder("placeholder", 0, None),
                 Token::Text(" text")
             ]
         );
@@ -207,7 +493,10 @@ mod tests {
         let tokens: Vec<Token> = TokenIterator::new("text {{placeholder}}", "{{", "}}").collect();
         assert_eq!(
             tokens,
-            vec![Token::Text("text "), Token::Placeholder("placeholder")]
+            vec![
+                Token::Text("text "),
+                Token::Placeholder("placeholder", 0, None)
+            ]
         );
     }
 
@@ -223,11 +512,11 @@ mod tests {
             tokens,
             vec![
                 Token::Text(""),
-                Token::Placeholder("placeholder"),
+                Token::Placeholder("placeholder", 0, None),
                 Token::Text(" text "),
-                Token::Placeholder("placeholder"),
+                Token::Placeholder("placeholder", 0, None),
                 Token::Text(" test "),
-                Token::Placeholder("placeholder")
+                Token::Placeholder("placeholder", 0, None)
             ]
         );
     }
@@ -255,7 +544,7 @@ mod tests {
             tokens,
             vec![
                 Token::Text("text "),
-                Token::Placeholder("placeholder"),
+                Token::Placeholder("placeholder", 0, None),
                 Token::Text(" "),
                 Token::Text("{{placeholder")
             ]
@@ -267,7 +556,10 @@ mod tests {
         let tokens: Vec<Token> = TokenIterator::new("text [ placeholder]", "[", "]").collect();
         assert_eq!(
             tokens,
-            vec![Token::Text("text "), Token::Placeholder("placeholder")]
+            vec![
+                Token::Text("text "),
+                Token::Placeholder("placeholder", 0, None)
+            ]
         );
     }
 
@@ -276,7 +568,10 @@ mod tests {
         let tokens: Vec<Token> = TokenIterator::new("text [placeholder ]", "[", "]").collect();
         assert_eq!(
             tokens,
-            vec![Token::Text("text "), Token::Placeholder("placeholder")]
+            vec![
+                Token::Text("text "),
+                Token::Placeholder("placeholder", 0, None)
+            ]
         );
     }
 
@@ -285,7 +580,370 @@ mod tests {
         let tokens: Vec<Token> = TokenIterator::new("text [ placeholder ]", "[", "]").collect();
         assert_eq!(
             tokens,
-            vec![Token::Text("text "), Token::Placeholder("placeholder")]
+            vec![
+                Token::Text("text "),
+                Token::Placeholder("placeholder", 0, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strict_valid_placeholders() {
+        let mut iter =
+            TokenIterator::new("text [ placeholder ]", "[", "]").strict(NameRules::default());
+        let tokens: Vec<Token> = iter.by_ref().collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text("text "),
+                Token::Placeholder("placeholder", 0, None)
+            ]
+        );
+        assert!(iter.take_error().is_none());
+    }
+
+    #[test]
+    fn test_strict_unclosed_placeholder() {
+        let mut iter =
+            TokenIterator::new("text [placeholder", "[", "]").strict(NameRules::default());
+        let tokens: Vec<Token> = iter.by_ref().collect();
+        assert_eq!(tokens, vec![Token::Text("text ")]);
+        assert!(matches!(iter.take_error(), Some(Error::Unclosed { span }) if span == (5..17)));
+    }
+
+    #[test]
+    fn test_strict_invalid_names() {
+        let cases = [
+            ("text {{  }}", "", 7..9, InvalidNameReason::Empty),
+            (
+                "{{a {{b}}",
+                "a {{b",
+                2..7,
+                InvalidNameReason::NestedDelimiter,
+            ),
+            (
+                "{{a\nb}}",
+                "a\nb",
+                2..5,
+                InvalidNameReason::InvalidChar('\n'),
+            ),
+        ];
+
+        for (text, expected_name, expected_span, expected_reason) in cases {
+            let mut iter = TokenIterator::new(text, "{{", "}}").strict(NameRules::default());
+            iter.by_ref().for_each(drop);
+
+            match iter.take_error() {
+                Some(Error::InvalidName { name, span, reason }) => {
+                    assert_eq!(name, expected_name);
+                    assert_eq!(span, expected_span);
+                    assert_eq!(reason, expected_reason);
+                }
+                other => panic!("unexpected result for {:?}: {:?}", text, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_strict_custom_rules() {
+        let rules = NameRules {
+            identifier: true,
+            max_len: Some(8),
+            ..NameRules::default()
+        };
+        let cases = [
+            ("{{1abc}}", InvalidNameReason::NotIdentifier),
+            ("{{first-name}}", InvalidNameReason::TooLong(8)),
+            ("{{a-b}}", InvalidNameReason::NotIdentifier),
+        ];
+
+        for (text, expected_reason) in cases {
+            let mut iter = TokenIterator::new(text, "{{", "}}").strict(rules);
+            iter.by_ref().for_each(drop);
+
+            match iter.take_error() {
+                Some(Error::InvalidName { reason, .. }) => assert_eq!(reason, expected_reason),
+                other => panic!("unexpected result for {:?}: {:?}", text, other),
+            }
+        }
+
+        let rules = NameRules {
+            allowed_chars: Some(|c| c.is_ascii_lowercase() || c == '.'),
+            ..NameRules::default()
+        };
+        let mut iter = TokenIterator::new("{{user.name}} {{User}}", "{{", "}}").strict(rules);
+        let tokens: Vec<Token> = iter.by_ref().collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text(""),
+                Token::Placeholder("user.name", 0, None),
+                Token::Text(" ")
+            ]
+        );
+        assert!(matches!(
+            iter.take_error(),
+            Some(Error::InvalidName {
+                reason: InvalidNameReason::InvalidChar('U'),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_trim_placeholder_unicode_whitespace() {
+        let tokens: Vec<Token> = TokenIterator::new(
+            "text {{\tplaceholder\n}} {{\u{3000}other\u{a0}}}",
+            "{{",
+            "}}",
+        )
+        .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text("text "),
+                Token::Placeholder("placeholder", 0, None),
+                Token::Text(" "),
+                Token::Placeholder("other", 0, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trim_markers() {
+        let tokens: Vec<Token> =
+            TokenIterator::new("<ul>\n  {{~ item ~}}\n</ul>", "{{", "}}").collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text("<ul>"),
+                Token::Placeholder("item", 0, None),
+                Token::Text("</ul>")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trim_marker_unclosed_keeps_whitespace() {
+        let tokens: Vec<Token> = TokenIterator::new("a  {{~ b", "{{", "}}").collect();
+        assert_eq!(tokens, vec![Token::Text("a  "), Token::Text("{{~ b")]);
+    }
+
+    #[test]
+    fn test_trim_markers_single_side() {
+        let tokens: Vec<Token> =
+            TokenIterator::new("a \n[~first] \n [second~] \n b", "[", "]").collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text("a"),
+                Token::Placeholder("first", 0, None),
+                Token::Text(" \n "),
+                Token::Placeholder("second", 0, None),
+                Token::Text("b")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trim_marker_alone_trims_before_empty_placeholder() {
+        // A lone `~` is a single leading trim marker around an empty name,
+        // rather than both a leading and a trailing one.
+        let tokens: Vec<Token> = TokenIterator::new("a [~] b", "[", "]").collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text("a"),
+                Token::Placeholder("", 0, None),
+                Token::Text(" b")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comments_are_skipped() {
+        let tokens: Vec<Token> =
+            TokenIterator::new("text {{! a note }}{{placeholder}} {{!}}text", "{{", "}}").collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text("text "),
+                Token::Text(""),
+                Token::Placeholder("placeholder", 0, None),
+                Token::Text(" "),
+                Token::Text("text")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comments_with_trim_markers() {
+        let tokens: Vec<Token> =
+            TokenIterator::new("text\n{{~! a note ~}}\ntext", "{{", "}}").collect();
+        assert_eq!(tokens, vec![Token::Text("text"), Token::Text("text")]);
+    }
+
+    #[test]
+    fn test_strict_comments_skip_name_rules() {
+        let rules = NameRules {
+            identifier: true,
+            ..NameRules::default()
+        };
+        let mut iter =
+            TokenIterator::new("[! not an identifier ] [placeholder]", "[", "]").strict(rules);
+        let tokens: Vec<Token> = iter.by_ref().collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text(""),
+                Token::Text(" "),
+                Token::Placeholder("placeholder", 0, None)
+            ]
+        );
+        assert!(iter.take_error().is_none());
+    }
+
+    #[test]
+    fn test_raw_block() {
+        let tokens: Vec<Token> = TokenIterator::new(
+            "text {{#raw}}{{placeholder}} {{/other}}{{ /raw }} {{placeholder}}",
+            "{{",
+            "}}",
+        )
+        .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text("text "),
+                Token::Text("{{placeholder}} {{/other}}"),
+                Token::Text(" "),
+                Token::Placeholder("placeholder", 0, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_raw_block_with_trim_markers() {
+        let tokens: Vec<Token> =
+            TokenIterator::new("a\n[~#raw~]\n [b] \n[~/raw~]\nc", "[", "]").collect();
+        assert_eq!(
+            tokens,
+            vec![Token::Text("a"), Token::Text("[b]"), Token::Text("c")]
+        );
+    }
+
+    #[test]
+    fn test_raw_block_unclosed() {
+        let tokens: Vec<Token> = TokenIterator::new("a [#raw] [b] c", "[", "]").collect();
+        assert_eq!(tokens, vec![Token::Text("a "), Token::Text("[#raw] [b] c")]);
+
+        let mut iter = TokenIterator::new("a [#raw] [b] c", "[", "]").strict(NameRules::default());
+        iter.by_ref().for_each(drop);
+        assert!(matches!(iter.take_error(), Some(Error::Unclosed { span }) if span == (2..14)));
+    }
+
+    #[test]
+    fn test_placeholder_named_raw() {
+        let tokens: Vec<Token> = TokenIterator::new("a [raw] [b]", "[", "]").collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text("a "),
+                Token::Placeholder("raw", 0, None),
+                Token::Text(" "),
+                Token::Placeholder("b", 0, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_delimiters() {
+        let tokens: Vec<Token> = TokenIterator::new(
+            "{{first}} {{=<% %>=}}{\"json\": {{x}}, <% second %>} <%={{ }}=%>{{third}}",
+            "{{",
+            "}}",
+        )
+        .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text(""),
+                Token::Placeholder("first", 0, None),
+                Token::Text(" "),
+                Token::Text("{\"json\": {{x}}, "),
+                Token::Placeholder("second", 1, None),
+                Token::Text("} "),
+                Token::Text(""),
+                Token::Placeholder("third", 2, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_delimiters_invalid_pairs() {
+        for text in ["a {{= =}} b", "a {{=| |=}} b", "a {{=<% %> |=}} b"] {
+            let tokens: Vec<Token> = TokenIterator::new(text, "{{", "}}").collect();
+            assert_eq!(
+                tokens,
+                vec![
+                    Token::Text("a "),
+                    Token::Text(&text[2..text.len() - 2]),
+                    Token::Text(" b")
+                ]
+            );
+
+            let mut iter = TokenIterator::new(text, "{{", "}}").strict(NameRules::default());
+            iter.by_ref().for_each(drop);
+            assert!(matches!(
+                iter.take_error(),
+                Some(Error::InvalidDelimiters { span }) if span == (4..text.len() - 4)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_multiple_delimiters() {
+        let iter = TokenIterator::with_delimiters(
+            "${first} {{second}} ${{third}}$ {{fourth",
+            &[("${", "}"), ("{{", "}}"), ("${{", "}}$")],
+        );
+        let tokens: Vec<Token> = iter.collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text(""),
+                Token::Placeholder("first", 0, None),
+                Token::Text(" "),
+                Token::Placeholder("second", 1, None),
+                Token::Text(" "),
+                Token::Placeholder("third", 2, None),
+                Token::Text(" "),
+                Token::Text("{{fourth")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multiple_delimiters_set_delimiters_replaces_opening_pair() {
+        let mut iter = TokenIterator::with_delimiters(
+            "{{=<% %>=}}<%first%> ${second} {{third}}",
+            &[("${", "}"), ("{{", "}}")],
+        );
+        let tokens: Vec<Token> = iter.by_ref().collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Text(""),
+                Token::Text(""),
+                Token::Placeholder("first", 2, None),
+                Token::Text(" "),
+                Token::Placeholder("second", 0, None),
+                Token::Text(" {{third}}")
+            ]
+        );
+        assert_eq!(
+            iter.delimiters(),
+            &[("${", "}"), ("{{", "}}"), ("<%", "%>")]
         );
     }
 }
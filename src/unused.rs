@@ -0,0 +1,166 @@
+use alloc::{string::ToString, vec::Vec};
+
+use crate::context::PlaceholderContext;
+use crate::error::{Error, Result};
+use crate::Template;
+
+impl<'t> Template<'t> {
+    /// Returns the keys of `context` that no placeholder of the template
+    /// refers to, sorted. These are usually stale or misspelled entries that
+    /// would otherwise be silently ignored.
+    ///
+    /// These are the sorted `extra` keys of [`Template::validate`] for the
+    /// keys of `context`, except the keys reached through a dotted
+    /// placeholder such as `user` for `{{user.first}}`, which is how nested
+    /// values are looked up. Only keys reported by [`PlaceholderContext::keys`]
+    /// are considered, which covers `HashMap`, `BTreeMap`, structs deriving
+    /// [`PlaceholderContext`] and, with the `struct_context` feature,
+    /// `serde_json::Value` objects.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use text_placeholder::Template;
+    /// # #[cfg(feature = "std")]
+    /// # use std::collections::HashMap;
+    /// # #[cfg(not(feature = "std"))]
+    /// # use hashbrown::HashMap;
+    /// let template = Template::new("Hello {{name}}!");
+    /// let context = HashMap::from([("name", "Ana"), ("nmae", "Bo"), ("city", "Lisbon")]);
+    ///
+    /// assert_eq!(template.unused_keys(&context), vec!["city", "nmae"]);
+    /// ```
+    pub fn unused_keys<'c, C>(&self, context: &'c C) -> Vec<&'c str>
+    where
+        C: PlaceholderContext + ?Sized,
+    {
+        let placeholders = self.placeholders();
+        // The extra keys of a report are already free of duplicates.
+        let mut unused = self.validate(context.keys()).extra;
+
+        unused.retain(|key| {
+            !placeholders
+                .iter()
+                .any(|name| name.split('.').next() == Some(*key))
+        });
+
+        unused.sort_unstable();
+        unused
+    }
+
+    /// Strict version of [`Template::unused_keys`], keys of `context` that no
+    /// placeholder refers to result in an `Error::UnusedKeys`.
+    pub fn check_context<C>(&self, context: &C) -> Result<()>
+    where
+        C: PlaceholderContext + ?Sized,
+    {
+        let unused = self.unused_keys(context);
+
+        if unused.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::UnusedKeys {
+            keys: unused.into_iter().map(ToString::to_string).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned, collections::BTreeMap, string::ToString, vec::Vec};
+
+    #[cfg(feature = "std")]
+    use std::collections::HashMap;
+
+    #[cfg(not(feature = "std"))]
+    use hashbrown::HashMap;
+
+    use crate::Template;
+
+    #[test]
+    fn test_unused_keys() {
+        let template = Template::new("{{a}} {{b}} {{a}} {{! c }}");
+
+        let mut table = HashMap::new();
+        table.insert("a", "1");
+        table.insert("d", "4");
+        table.insert("c", "3");
+
+        assert_eq!(template.unused_keys(&table), ["c", "d"]);
+    }
+
+    #[test]
+    fn test_unused_keys_none() {
+        let template = Template::new("{{a}} {{b}}");
+        let table = BTreeMap::from([("a", "1"), ("b", "2")]);
+
+        assert!(template.unused_keys(&table).is_empty());
+        assert!(template.check_context(&table).is_ok());
+    }
+
+    #[test]
+    fn test_unused_keys_without_keys() {
+        struct Context;
+
+        impl crate::PlaceholderContext for Context {
+            fn get(&self, _: &str) -> Option<alloc::borrow::Cow<'_, str>> {
+                None
+            }
+        }
+
+        assert_eq!(
+            Template::new("text").unused_keys(&Context),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn test_check_context() {
+        let template = Template::new("Hello {{name}}!");
+
+        let mut table = HashMap::new();
+        table.insert("name", "Ana");
+        table.insert("nmae", "Bo");
+        table.insert("city", "Lisbon");
+
+        assert_eq!(
+            template.check_context(&table).map_err(|e| e.to_string()),
+            Err("Error while checking context. Reason: keys 'city', 'nmae' are not used by any placeholder.".to_owned())
+        );
+    }
+
+    #[cfg(feature = "struct_context")]
+    #[test]
+    fn test_unused_keys_struct() {
+        #[derive(serde::Serialize)]
+        struct Context {
+            name: &'static str,
+            retries: u8,
+        }
+
+        let context = serde_json::to_value(Context {
+            name: "Ana",
+            retries: 3,
+        })
+        .unwrap();
+
+        assert_eq!(Template::new("{{name}}").unused_keys(&context), ["retries"]);
+    }
+
+    #[cfg(feature = "struct_context")]
+    #[test]
+    fn test_unused_keys_nested_value() {
+        let context = serde_json::json!({
+            "user": { "first": "Ana", "last": "Silva" },
+            "team": { "name": "Core" },
+            "retries": 3,
+        });
+
+        let template = Template::new("{{user.first}} {{user.last}} {{retries}}");
+
+        assert_eq!(template.unused_keys(&context), ["team"]);
+        assert!(Template::new("{{user.first}} {{team.name}} {{retries}}")
+            .check_context(&context)
+            .is_ok());
+    }
+}
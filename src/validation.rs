@@ -0,0 +1,107 @@
+use core::fmt;
+
+/// Rules enforced on placeholder names when a template is parsed strictly,
+/// see [`Template::new_with_rules`](crate::Template::new_with_rules).
+///
+/// Regardless of the rules, strict parsing always rejects names that are
+/// empty, contain control characters (such as newlines) or contain one of
+/// the boundaries.
+///
+/// Example:
+/// ```rust
+/// # use text_placeholder::NameRules;
+/// let rules = NameRules {
+///     identifier: true,
+///     max_len: Some(32),
+///     ..NameRules::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NameRules {
+    /// Require names to look like identifiers: an ASCII letter or `_`
+    /// followed by ASCII letters, digits or `_`.
+    pub identifier: bool,
+    /// Only accept names whose characters are all allowed by this function.
+    pub allowed_chars: Option<fn(char) -> bool>,
+    /// Maximum length of a name in bytes.
+    pub max_len: Option<usize>,
+}
+
+/// The reason why a placeholder name was rejected by strict parsing.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InvalidNameReason {
+    /// The name is empty.
+    Empty,
+    /// The name contains one of the template boundaries.
+    NestedDelimiter,
+    /// The name contains a character that is not allowed.
+    InvalidChar(char),
+    /// The name is not identifier-like, see [`NameRules::identifier`].
+    NotIdentifier,
+    /// The name is longer than [`NameRules::max_len`].
+    TooLong(usize),
+}
+
+impl NameRules {
+    pub(crate) fn check(
+        &self,
+        name: &str,
+        start: &str,
+        end: &str,
+    ) -> core::result::Result<(), InvalidNameReason> {
+        if name.is_empty() {
+            return Err(InvalidNameReason::Empty);
+        }
+
+        if name.contains(start) || name.contains(end) {
+            return Err(InvalidNameReason::NestedDelimiter);
+        }
+
+        if let Some(c) = name.chars().find(|c| c.is_control()) {
+            return Err(InvalidNameReason::InvalidChar(c));
+        }
+
+        if let Some(max_len) = self.max_len {
+            if name.len() > max_len {
+                return Err(InvalidNameReason::TooLong(max_len));
+            }
+        }
+
+        if let Some(allowed) = self.allowed_chars {
+            if let Some(c) = name.chars().find(|c| !allowed(*c)) {
+                return Err(InvalidNameReason::InvalidChar(c));
+            }
+        }
+
+        if self.identifier && !is_identifier(name) {
+            return Err(InvalidNameReason::NotIdentifier);
+        }
+
+        Ok(())
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+impl fmt::Display for InvalidNameReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidNameReason::Empty => write!(f, "the name is empty"),
+            InvalidNameReason::NestedDelimiter => write!(f, "the name contains a boundary"),
+            InvalidNameReason::InvalidChar(c) => write!(f, "the character {c:?} is not allowed"),
+            InvalidNameReason::NotIdentifier => write!(f, "the name is not an identifier"),
+            InvalidNameReason::TooLong(max) => {
+                write!(f, "the name is longer than {max} bytes")
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! # Derive macro for `text_placeholder`
+//!
+//! Generates an implementation of `text_placeholder::PlaceholderContext` for
+//! structs with named fields, so they can be used as a context without
+//! serializing them through `serde_json`.
+//!
+//! Each field is exposed as a placeholder with the same name. The following
+//! field attributes are supported:
+//!
+//! - `#[placeholder(rename = "name")]` exposes the field under another name.
+//! - `#[placeholder(skip)]` does not expose the field at all.
+//!
+//! This crate is not meant to be used directly, enable the `derive` feature
+//! of `text_placeholder` instead.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+#[proc_macro_derive(PlaceholderContext, attributes(placeholder))]
+pub fn derive_placeholder_context(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "PlaceholderContext can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "PlaceholderContext can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut arms = Vec::new();
+    let mut keys = Vec::new();
+    let mut present = Vec::new();
+
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named fields have an ident");
+        // Raw identifiers such as `r#type` are exposed as `type`.
+        let ident_name = ident.to_string();
+        let mut key = ident_name
+            .strip_prefix("r#")
+            .unwrap_or(&ident_name)
+            .to_string();
+        let mut skip = false;
+
+        for attr in &field.attrs {
+            if !attr.path().is_ident("placeholder") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    key = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename = \"...\"` or `skip`"))
+                }
+            })?;
+        }
+
+        if skip {
+            continue;
+        }
+
+        if keys.contains(&key) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!("duplicate placeholder name `{}`", key),
+            ));
+        }
+
+        arms.push(quote! {
+            #key => ::text_placeholder::PlaceholderValue::to_placeholder(&self.#ident),
+        });
+        // Fields without a value, such as `None`, are not listed as keys.
+        present.push(quote! {
+            if ::text_placeholder::PlaceholderValue::to_placeholder(&self.#ident).is_some() {
+                keys.push(#key);
+            }
+        });
+        keys.push(key);
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::text_placeholder::PlaceholderContext for #name #ty_generics #where_clause {
+            fn get(
+                &self,
+                key: &str,
+            ) -> ::core::option::Option<::text_placeholder::__private::Cow<'_, str>> {
+                match key {
+                    #(#arms)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            fn keys(&self) -> ::text_placeholder::__private::Vec<&str> {
+                let mut keys = ::text_placeholder::__private::Vec::new();
+                #(#present)*
+                keys
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_duplicate_names_are_rejected() {
+        let input = parse_quote! {
+            struct Context {
+                name: String,
+                #[placeholder(rename = "name")]
+                other: String,
+            }
+        };
+
+        let err = expand(input).unwrap_err();
+
+        assert_eq!(err.to_string(), "duplicate placeholder name `name`");
+    }
+
+    #[test]
+    fn test_skipped_fields_do_not_conflict() {
+        let input = parse_quote! {
+            struct Context {
+                name: String,
+                #[placeholder(skip)]
+                other: String,
+            }
+        };
+
+        assert!(expand(input).is_ok());
+    }
+}